@@ -9,19 +9,23 @@ async fn main() -> hyprland::Result<()> {
     // Create a event listener
     let mut event_listener = AsyncEventListener::new();
 
-    event_listener.add_workspace_changed_handler(async_closure! {
-        |data| println!("{data:#?}")
-    });
+    event_listener.add_workspace_changed_handler(async |data| println!("{data:#?}"));
 
-    event_listener.add_fullscreen_state_changed_handler(async_closure! {
-        |fstate| println!("Window {} fullscreen", if fstate { "is" } else { "is not" })
+    event_listener.add_fullscreen_state_changed_handler(async |fstate| {
+        println!("Window {} fullscreen", if fstate { "is" } else { "is not" })
     });
 
-    event_listener.add_active_monitor_changed_handler(async_closure! {
-        |state| println!("Monitor state: {state:#?}")
+    event_listener
+        .add_active_monitor_changed_handler(async |state| println!("Monitor state: {state:#?}"));
+
+    // the future can't borrow what the handler captures, so it gets a clone
+    let prefix = String::from("workspace changed to");
+    event_listener.add_workspace_changed_handler(move |id| {
+        let prefix = prefix.clone();
+        async move { println!("{prefix} {id:?}") }
     });
 
-    // add event, yes functions and closures both work!
+    // the async_closure! macro still works too
     event_listener.add_workspace_changed_handler(async_closure! {
         |id| println!("workspace changed to {id:?}")
     });
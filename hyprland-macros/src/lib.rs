@@ -10,6 +10,9 @@ use syn::{
 };
 
 /// Creates a async closure
+///
+/// Event listeners take native async closures (`async |data| { ... }`), this macro is kept so
+/// existing handlers keep compiling
 #[proc_macro]
 //#[deprecated(since="0.4.0-beta.3", note="Use the async closure syntax instead.")]
 pub fn async_closure(input: TokenStream) -> TokenStream {
@@ -2,13 +2,30 @@
 //!
 
 use crate::dispatch::{DispatchType, gen_dispatch_str};
-use crate::keyword::Keyword;
+use crate::keyword::{Keyword, OptionValue};
+
+/// Module providing builders for animations and beziers
+#[cfg(feature = "data")]
+pub mod animations;
+/// Module providing a builder for per-device input configs
+#[cfg(feature = "data")]
+pub mod devices;
+/// Module providing typed keys for binds
+pub mod keysym;
+/// Module providing a builder for layer rules
+pub mod layer_rules;
+/// Module providing a builder for monitor configs
+pub mod monitors;
+/// Module providing a builder for window rules
+pub mod window_rules;
 
 /// Module providing stuff for adding an removing keybinds
 pub mod binds {
     use super::*;
     use crate::default_instance;
+    use crate::dispatch::SubmapParam;
     use crate::instance::Instance;
+    use crate::shared::command;
 
     trait Join: IntoIterator {
         fn join(&self) -> String;
@@ -26,6 +43,14 @@ pub mod binds {
         ),
         /// Variant for a regular key
         Key(&'a str),
+        /// Variant for a typed key
+        Sym(Keysym),
+    }
+
+    impl From<Keysym> for Key<'_> {
+        fn from(keysym: Keysym) -> Self {
+            Key::Sym(keysym)
+        }
     }
 
     impl std::fmt::Display for Key<'_> {
@@ -36,11 +61,13 @@ pub mod binds {
                 match self {
                     Key::Mod(m, s) => format!("{}_{s}", m.join()),
                     Key::Key(s) => s.to_string(),
+                    Key::Sym(k) => k.to_string(),
                 }
             )
         }
     }
 
+    pub use crate::config::keysym::Keysym;
     pub use crate::shared::Mod;
 
     impl Join for Vec<Mod> {
@@ -139,6 +166,8 @@ pub mod binds {
         pub flags: &'a [Flag],
         /// The dispatcher to be called once complete
         pub dispatcher: DispatchType<'a>,
+        /// The description of the bind, used when it has [Flag::d]
+        pub description: Option<&'a str>,
     }
 
     /// Struct to hold methods for adding and removing binds
@@ -153,12 +182,17 @@ pub mod binds {
             Binding {
                 mods,
                 key,
+                flags,
                 dispatcher,
-                ..
+                description,
             }: Binding,
         ) -> crate::Result<String> {
+            let description = match flags.contains(&Flag::d) {
+                true => format!("{},", description.unwrap_or_default()),
+                false => String::new(),
+            };
             Ok(format!(
-                "{partial},{dispatcher}",
+                "{partial},{description}{dispatcher}",
                 partial = Self::gen_str_partial(PartialBind { mods, key }),
                 dispatcher = gen_dispatch_str(dispatcher, false)?.data
             ))
@@ -222,6 +256,546 @@ pub mod binds {
             )
             .await
         }
+
+        /// Returns the binds a binding would conflict with in a submap ([None] for the global one)
+        ///
+        /// Binds conflict when they are triggered by the same key with equivalent mods
+        /// (any mods if the binding has [Flag::i]), on the same edge ([Flag::r]) and
+        /// for the same device ([Flag::m]). Transparent ([Flag::t]) bindings never conflict.
+        /// Keys are compared case insensitively, so a keysym and a `code:` key for the same
+        /// physical key are not detected
+        #[cfg(feature = "data")]
+        pub fn conflicts<'b>(
+            binding: &Binding,
+            submap: Option<&str>,
+            binds: impl IntoIterator<Item = &'b crate::data::Bind>,
+        ) -> Vec<&'b crate::data::Bind> {
+            if binding.flags.contains(&Flag::t) {
+                return vec![];
+            }
+            let (modmask, key) = OwnedBinding::combo(binding);
+            let ignore_mods = binding.flags.contains(&Flag::i);
+            binds
+                .into_iter()
+                .filter(|bind| {
+                    let live = OwnedBinding::from(*bind);
+                    live.submap.as_deref() == submap
+                        && bind.mouse == binding.flags.contains(&Flag::m)
+                        && bind.release == binding.flags.contains(&Flag::r)
+                        && (ignore_mods || bind.modmask == modmask)
+                        && live.key.eq_ignore_ascii_case(&key)
+                })
+                .collect()
+        }
+
+        /// Returns the live binds a binding would conflict with in a submap ([None] for the global one)
+        #[cfg(feature = "data")]
+        pub fn find_conflicts(
+            binding: &Binding,
+            submap: Option<&str>,
+        ) -> crate::Result<Vec<crate::data::Bind>> {
+            Self::instance_find_conflicts(default_instance()?, binding, submap)
+        }
+
+        /// Returns the live binds a binding would conflict with in a submap ([None] for the global one)
+        #[cfg(feature = "data")]
+        pub fn instance_find_conflicts(
+            instance: &Instance,
+            binding: &Binding,
+            submap: Option<&str>,
+        ) -> crate::Result<Vec<crate::data::Bind>> {
+            use crate::shared::HyprData;
+            let binds = crate::data::Binds::instance_get(instance)?;
+            Ok(Self::conflicts(binding, submap, &binds)
+                .into_iter()
+                .cloned()
+                .collect())
+        }
+
+        /// Returns the live binds a binding would conflict with in a submap ([None] for the global one) (async)
+        #[cfg(all(feature = "data", any(feature = "async-lite", feature = "tokio")))]
+        pub async fn find_conflicts_async(
+            binding: &Binding<'_>,
+            submap: Option<&str>,
+        ) -> crate::Result<Vec<crate::data::Bind>> {
+            Self::instance_find_conflicts_async(default_instance()?, binding, submap).await
+        }
+
+        /// Returns the live binds a binding would conflict with in a submap ([None] for the global one) (async)
+        #[cfg(all(feature = "data", any(feature = "async-lite", feature = "tokio")))]
+        pub async fn instance_find_conflicts_async(
+            instance: &Instance,
+            binding: &Binding<'_>,
+            submap: Option<&str>,
+        ) -> crate::Result<Vec<crate::data::Bind>> {
+            use crate::shared::HyprData;
+            let binds = crate::data::Binds::instance_get_async(instance).await?;
+            Ok(Self::conflicts(binding, submap, &binds)
+                .into_iter()
+                .cloned()
+                .collect())
+        }
+
+        /// Binds a keybinding, failing with [HyprError::BindConflict][crate::error::HyprError::BindConflict]
+        /// if it conflicts with a live bind
+        #[cfg(feature = "data")]
+        pub fn bind_checked(binding: Binding) -> crate::Result<()> {
+            Self::instance_bind_checked(default_instance()?, binding)
+        }
+
+        /// Binds a keybinding, failing with [HyprError::BindConflict][crate::error::HyprError::BindConflict]
+        /// if it conflicts with a live bind
+        #[cfg(feature = "data")]
+        pub fn instance_bind_checked(instance: &Instance, binding: Binding) -> crate::Result<()> {
+            let conflicts = Self::instance_find_conflicts(instance, &binding, None)?;
+            if !conflicts.is_empty() {
+                return Err(crate::error::HyprError::BindConflict(conflicts));
+            }
+            Self::instance_bind(instance, binding)
+        }
+
+        /// Binds a keybinding, failing with [HyprError::BindConflict][crate::error::HyprError::BindConflict]
+        /// if it conflicts with a live bind (async)
+        #[cfg(all(feature = "data", any(feature = "async-lite", feature = "tokio")))]
+        pub async fn bind_checked_async(binding: Binding<'_>) -> crate::Result<()> {
+            Self::instance_bind_checked_async(default_instance()?, binding).await
+        }
+
+        /// Binds a keybinding, failing with [HyprError::BindConflict][crate::error::HyprError::BindConflict]
+        /// if it conflicts with a live bind (async)
+        #[cfg(all(feature = "data", any(feature = "async-lite", feature = "tokio")))]
+        pub async fn instance_bind_checked_async(
+            instance: &Instance,
+            binding: Binding<'_>,
+        ) -> crate::Result<()> {
+            let conflicts = Self::instance_find_conflicts_async(instance, &binding, None).await?;
+            if !conflicts.is_empty() {
+                return Err(crate::error::HyprError::BindConflict(conflicts));
+            }
+            Self::instance_bind_async(instance, binding).await
+        }
+    }
+
+    /// The flags a bind can have, in the order Hyprland expects them
+    const FLAG_ORDER: [Flag; 10] = [
+        Flag::l,
+        Flag::r,
+        Flag::e,
+        Flag::n,
+        Flag::m,
+        Flag::t,
+        Flag::i,
+        Flag::s,
+        Flag::d,
+        Flag::p,
+    ];
+
+    /// An owned key bind, which can be decoded from a [Bind][crate::data::Bind] or built from a
+    /// [Binding], and compared with other ones
+    ///
+    /// Mods and flags are kept in a normalized order so equal binds compare equal
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct OwnedBinding {
+        /// The mods, in the order of [Mod::ALL]
+        pub mods: Vec<Mod>,
+        /// The key (`code:NN` for binds made with a keycode)
+        pub key: String,
+        /// The bind flags
+        pub flags: Vec<Flag>,
+        /// The name of the dispatcher
+        pub dispatcher: String,
+        /// The argument of the dispatcher
+        pub arg: String,
+        /// The submap the bind is in, [None] for the global one
+        pub submap: Option<String>,
+        /// The description of the bind, from `bindd`
+        pub description: Option<String>,
+    }
+
+    impl OwnedBinding {
+        fn normalize_flags(flags: &[Flag]) -> Vec<Flag> {
+            FLAG_ORDER
+                .into_iter()
+                .filter(|flag| flags.contains(flag))
+                .collect()
+        }
+
+        /// The modmask and key of a binding, including the mods of [Key::Mod]
+        fn combo(binding: &Binding) -> (u16, String) {
+            match &binding.key {
+                Key::Mod(mods, key) => (
+                    Mod::to_modmask(binding.mods) | Mod::to_modmask(mods),
+                    key.to_string(),
+                ),
+                Key::Key(key) => (Mod::to_modmask(binding.mods), key.to_string()),
+                Key::Sym(key) => (Mod::to_modmask(binding.mods), key.to_string()),
+            }
+        }
+
+        /// Parses the dispatcher and its argument
+        pub fn dispatch_type(&self) -> DispatchType<'_> {
+            DispatchType::parse(&self.dispatcher, &self.arg)
+        }
+
+        /// Borrows the bind as a [Binding], to register it with [Binder]
+        pub fn as_binding(&self) -> Binding<'_> {
+            Binding {
+                mods: &self.mods,
+                key: Key::Key(&self.key),
+                flags: &self.flags,
+                dispatcher: self.dispatch_type(),
+                description: self.description.as_deref(),
+            }
+        }
+
+        /// Borrows the bind as a [PartialBind], to unbind it with [Binder]
+        pub fn as_partial_bind(&self) -> PartialBind<'_> {
+            PartialBind {
+                mods: &self.mods,
+                key: Key::Key(&self.key),
+            }
+        }
+    }
+
+    impl TryFrom<&Binding<'_>> for OwnedBinding {
+        type Error = crate::error::HyprError;
+
+        fn try_from(binding: &Binding<'_>) -> crate::Result<Self> {
+            let dispatcher = gen_dispatch_str(binding.dispatcher.clone(), false)?.data;
+            let (dispatcher, arg) = dispatcher.split_once(',').unwrap_or((&dispatcher, ""));
+            let (modmask, key) = Self::combo(binding);
+            Ok(Self {
+                mods: Mod::from_modmask(modmask),
+                key,
+                flags: Self::normalize_flags(binding.flags),
+                dispatcher: dispatcher.to_string(),
+                arg: arg.to_string(),
+                submap: None,
+                description: binding
+                    .flags
+                    .contains(&Flag::d)
+                    .then(|| binding.description.unwrap_or_default().to_string()),
+            })
+        }
+    }
+
+    #[cfg(feature = "data")]
+    impl From<&crate::data::Bind> for OwnedBinding {
+        fn from(bind: &crate::data::Bind) -> Self {
+            let flags = [
+                (bind.locked, Flag::l),
+                (bind.release, Flag::r),
+                (bind.repeat, Flag::e),
+                (bind.mouse, Flag::m),
+                (!bind.description.is_empty(), Flag::d),
+            ]
+            .into_iter()
+            .filter_map(|(set, flag)| set.then_some(flag))
+            .collect();
+            Self {
+                mods: Mod::from_modmask(bind.modmask),
+                key: if bind.key.is_empty() && bind.keycode > 0 {
+                    format!("code:{}", bind.keycode)
+                } else {
+                    bind.key.clone()
+                },
+                flags,
+                dispatcher: bind.dispatcher.clone(),
+                arg: bind.arg.clone(),
+                submap: parse_submap(&bind.submap),
+                description: (!bind.description.is_empty()).then(|| bind.description.clone()),
+            }
+        }
+    }
+
+    /// A builder for a submap and the binds inside of it
+    ///
+    /// ```rust
+    /// use hyprland::config::binds::*;
+    /// use hyprland::dispatch::DispatchType;
+    /// let submap = Submap::new("resize")
+    ///     .enter(PartialBind { mods: &[Mod::SUPER], key: Key::Key("r") })
+    ///     .bind(Binding {
+    ///         mods: &[],
+    ///         key: Key::Key("l"),
+    ///         flags: &[Flag::e],
+    ///         dispatcher: DispatchType::Custom("resizeactive", "10 0"),
+    ///         description: None,
+    ///     })
+    ///     .escape(PartialBind { mods: &[], key: Key::Key("escape") });
+    /// assert_eq!(
+    ///     submap.to_config_lines().ok(),
+    ///     Some(vec![
+    ///         "bind = SUPER,r,submap,resize".to_string(),
+    ///         "submap = resize".to_string(),
+    ///         "binde = ,l,resizeactive,10 0".to_string(),
+    ///         "bind = ,escape,submap,reset".to_string(),
+    ///         "submap = reset".to_string(),
+    ///     ])
+    /// );
+    /// ```
+    #[derive(Debug, Clone)]
+    pub struct Submap<'a> {
+        /// The name of the submap
+        pub name: &'a str,
+        /// The binds inside of the submap
+        pub binds: Vec<Binding<'a>>,
+        /// A global bind entering the submap
+        pub enter: Option<PartialBind<'a>>,
+        /// A bind inside of the submap going back to the global one
+        pub escape: Option<PartialBind<'a>>,
+    }
+
+    impl<'a> Submap<'a> {
+        /// Creates a new empty submap
+        pub fn new(name: &'a str) -> Self {
+            Self {
+                name,
+                binds: vec![],
+                enter: None,
+                escape: None,
+            }
+        }
+
+        /// Adds a bind to the submap
+        pub fn bind(mut self, binding: Binding<'a>) -> Self {
+            self.binds.push(binding);
+            self
+        }
+
+        /// Sets the global bind entering the submap
+        pub fn enter(mut self, bind: PartialBind<'a>) -> Self {
+            self.enter = Some(bind);
+            self
+        }
+
+        /// Sets the bind resetting to the global submap
+        pub fn escape(mut self, bind: PartialBind<'a>) -> Self {
+            self.escape = Some(bind);
+            self
+        }
+
+        fn submap_bind(
+            PartialBind { mods, key }: PartialBind<'a>,
+            param: SubmapParam<'a>,
+        ) -> Binding<'a> {
+            Binding {
+                mods,
+                key,
+                flags: &[],
+                dispatcher: DispatchType::Submap(param),
+                description: None,
+            }
+        }
+
+        /// Returns the keywords and values to set, in order
+        pub fn keywords(&self) -> crate::Result<Vec<(String, OptionValue)>> {
+            if self.name.is_empty() || self.name == "reset" || self.name.contains(',') {
+                return Err(crate::error::HyprError::InvalidConfig(format!(
+                    "invalid submap name {:?}",
+                    self.name
+                )));
+            }
+            let bind = |binding: Binding<'a>| -> crate::Result<(String, OptionValue)> {
+                Ok((
+                    format!("bind{}", (&binding.flags).join()),
+                    OptionValue::String(Binder::gen_str(binding)?),
+                ))
+            };
+            let mut keywords = vec![];
+            if let Some(enter) = self.enter.clone() {
+                keywords.push(bind(Self::submap_bind(
+                    enter,
+                    SubmapParam::Name(self.name),
+                ))?);
+            }
+            keywords.push((
+                "submap".to_string(),
+                OptionValue::String(self.name.to_string()),
+            ));
+            for binding in &self.binds {
+                keywords.push(bind(binding.clone())?);
+            }
+            if let Some(escape) = self.escape.clone() {
+                keywords.push(bind(Self::submap_bind(escape, SubmapParam::Reset))?);
+            }
+            keywords.push((
+                "submap".to_string(),
+                OptionValue::String("reset".to_string()),
+            ));
+            Ok(keywords)
+        }
+
+        /// Returns the submap as lines for a Hyprland config file
+        pub fn to_config_lines(&self) -> crate::Result<Vec<String>> {
+            Ok(self
+                .keywords()?
+                .into_iter()
+                .map(|(key, value)| format!("{key} = {value}"))
+                .collect())
+        }
+
+        /// Registers the submap and its binds
+        pub fn register(&self) -> crate::Result<()> {
+            self.instance_register(default_instance()?)
+        }
+
+        /// Registers the submap and its binds
+        pub fn instance_register(&self, instance: &Instance) -> crate::Result<()> {
+            for (key, value) in self.keywords()? {
+                if let Err(e) = Keyword::instance_set(instance, key, value) {
+                    // don't leave the following binds inside of the submap
+                    let reset = OptionValue::String("reset".to_string());
+                    let _ = Keyword::instance_set(instance, "submap", reset);
+                    return Err(e);
+                }
+            }
+            Ok(())
+        }
+
+        /// Registers the submap and its binds (async)
+        #[cfg(any(feature = "async-lite", feature = "tokio"))]
+        pub async fn register_async(&self) -> crate::Result<()> {
+            self.instance_register_async(default_instance()?).await
+        }
+
+        /// Registers the submap and its binds (async)
+        #[cfg(any(feature = "async-lite", feature = "tokio"))]
+        pub async fn instance_register_async(&self, instance: &Instance) -> crate::Result<()> {
+            for (key, value) in self.keywords()? {
+                if let Err(e) = Keyword::instance_set_async(instance, key, value).await {
+                    // don't leave the following binds inside of the submap
+                    let reset = OptionValue::String("reset".to_string());
+                    let _ = Keyword::instance_set_async(instance, "submap", reset).await;
+                    return Err(e);
+                }
+            }
+            Ok(())
+        }
+
+        /// Returns the name of the active submap, [None] if it is the global one
+        pub fn current() -> crate::Result<Option<String>> {
+            Self::instance_current(default_instance()?)
+        }
+
+        /// Returns the name of the active submap, [None] if it is the global one
+        pub fn instance_current(instance: &Instance) -> crate::Result<Option<String>> {
+            Ok(parse_submap(
+                &instance.write_to_socket(command!(Empty, "submap"))?,
+            ))
+        }
+
+        /// Returns the name of the active submap, [None] if it is the global one (async)
+        #[cfg(any(feature = "async-lite", feature = "tokio"))]
+        pub async fn current_async() -> crate::Result<Option<String>> {
+            Self::instance_current_async(default_instance()?).await
+        }
+
+        /// Returns the name of the active submap, [None] if it is the global one (async)
+        #[cfg(any(feature = "async-lite", feature = "tokio"))]
+        pub async fn instance_current_async(instance: &Instance) -> crate::Result<Option<String>> {
+            Ok(parse_submap(
+                &instance
+                    .write_to_socket_async(command!(Empty, "submap"))
+                    .await?,
+            ))
+        }
+    }
+
+    /// Hyprland reports the global submap as `default` from hyprctl and as an empty string in events
+    fn parse_submap(name: &str) -> Option<String> {
+        match name.trim() {
+            "" | "default" => None,
+            name => Some(name.to_string()),
+        }
+    }
+
+    /// Tracks the active submap and the binds that are live in it
+    ///
+    /// Feed it the data of [SubMapChanged][crate::event_listener::Event::SubMapChanged] events
+    /// to keep it up to date
+    #[cfg(feature = "data")]
+    #[derive(Debug, Clone)]
+    pub struct SubmapTracker {
+        submap: Option<String>,
+        binds: Vec<crate::data::Bind>,
+    }
+
+    #[cfg(feature = "data")]
+    impl SubmapTracker {
+        /// Creates a tracker from the current state
+        pub fn new() -> crate::Result<Self> {
+            Self::instance_new(default_instance()?)
+        }
+
+        /// Creates a tracker from the current state
+        pub fn instance_new(instance: &Instance) -> crate::Result<Self> {
+            use crate::shared::HyprData;
+            Ok(Self::from_parts(
+                Submap::instance_current(instance)?,
+                crate::data::Binds::instance_get(instance)?
+                    .into_iter()
+                    .collect(),
+            ))
+        }
+
+        /// Creates a tracker from the current state (async)
+        #[cfg(any(feature = "async-lite", feature = "tokio"))]
+        pub async fn new_async() -> crate::Result<Self> {
+            Self::instance_new_async(default_instance()?).await
+        }
+
+        /// Creates a tracker from the current state (async)
+        #[cfg(any(feature = "async-lite", feature = "tokio"))]
+        pub async fn instance_new_async(instance: &Instance) -> crate::Result<Self> {
+            use crate::shared::HyprData;
+            Ok(Self::from_parts(
+                Submap::instance_current_async(instance).await?,
+                crate::data::Binds::instance_get_async(instance)
+                    .await?
+                    .into_iter()
+                    .collect(),
+            ))
+        }
+
+        /// Creates a tracker from an already known submap and binds
+        pub fn from_parts(submap: Option<String>, binds: Vec<crate::data::Bind>) -> Self {
+            Self { submap, binds }
+        }
+
+        /// Updates the active submap from the data of a submap event
+        pub fn set_submap(&mut self, name: &str) {
+            self.submap = parse_submap(name);
+        }
+
+        /// Replaces the known binds, for example after registering a [Submap]
+        pub fn set_binds(&mut self, binds: Vec<crate::data::Bind>) {
+            self.binds = binds;
+        }
+
+        /// Updates the tracker from an event, returns true if the event was a submap change
+        #[cfg(feature = "listener")]
+        pub fn handle_event(&mut self, event: &crate::event_listener::Event) -> bool {
+            match event {
+                crate::event_listener::Event::SubMapChanged(name) => {
+                    self.set_submap(name);
+                    true
+                }
+                _ => false,
+            }
+        }
+
+        /// The active submap, [None] if it is the global one
+        pub fn submap(&self) -> Option<&str> {
+            self.submap.as_deref()
+        }
+
+        /// The binds that are live in the active submap
+        pub fn live_binds(&self) -> impl Iterator<Item = &crate::data::Bind> {
+            self.binds
+                .iter()
+                .filter(|bind| parse_submap(&bind.submap).as_deref() == self.submap.as_deref())
+        }
     }
 
     /// Very macro basic abstraction over [Binder] for internal use, **Dont use this instead use [crate::bind]**
@@ -247,6 +821,7 @@ pub mod binds {
                 key: $key,
                 flags: $flags,
                 dispatcher: $dis,
+                description: None,
             };
             Binder::instance_bind_async($instance, binding)
         }};
@@ -257,6 +832,7 @@ pub mod binds {
                 key: $key,
                 flags: $flags,
                 dispatcher: $dis,
+                description: None,
             };
             Binder::bind_async(binding)
         }};
@@ -267,6 +843,7 @@ pub mod binds {
                 key: $key,
                 flags: $flags,
                 dispatcher: $dis,
+                description: None,
             };
             Binder::instance_bind($instance, binding)
         }};
@@ -277,6 +854,7 @@ pub mod binds {
                 key: $key,
                 flags: $flags,
                 dispatcher: $dis,
+                description: None,
             };
             Binder::bind(binding)
         }};
@@ -456,6 +1034,7 @@ fn test_binds() {
         key: Key::Key("v"),
         flags: &[],
         dispatcher: DispatchType::ToggleFloating(None),
+        description: None,
     };
     let built_bind = match Binder::gen_str(binding) {
         Ok(v) => v,
@@ -463,3 +1042,203 @@ fn test_binds() {
     };
     assert_eq!(built_bind, "SUPER,v,togglefloating");
 }
+
+#[test]
+fn test_submap() {
+    use crate::dispatch::SubmapParam;
+    use binds::*;
+    let submap = Submap::new("launch")
+        .bind(Binding {
+            mods: &[],
+            key: Key::Key("f"),
+            flags: &[],
+            dispatcher: DispatchType::Exec("firefox"),
+            description: None,
+        })
+        .bind(Binding {
+            mods: &[],
+            key: Key::Key("f"),
+            flags: &[],
+            dispatcher: DispatchType::Submap(SubmapParam::Reset),
+            description: None,
+        });
+    let keywords = match submap.keywords() {
+        Ok(v) => v,
+        Err(e) => panic!("Error occured: {e}"), // Note to greppers: this is in a test!
+    };
+    let keywords: Vec<_> = keywords.iter().map(|(k, v)| format!("{k} {v}")).collect();
+    assert_eq!(
+        keywords,
+        [
+            "submap launch",
+            "bind ,f,exec,firefox",
+            "bind ,f,submap,reset",
+            "submap reset"
+        ]
+    );
+    assert!(Submap::new("reset").keywords().is_err());
+    let fade = Submap::new("fade").keywords().unwrap_or_default();
+    assert!(matches!(&fade[0], (k, OptionValue::String(v)) if k == "submap" && v == "fade"));
+}
+
+#[cfg(feature = "data")]
+#[test]
+fn test_submap_tracker() {
+    use crate::data::Bind;
+    use binds::*;
+    let bind = |submap: &str, key: &str| Bind {
+        locked: false,
+        mouse: false,
+        release: false,
+        repeat: false,
+        modmask: 0,
+        submap: submap.to_string(),
+        key: key.to_string(),
+        keycode: 0,
+        dispatcher: "exec".to_string(),
+        arg: String::new(),
+        description: String::new(),
+    };
+    let mut tracker = SubmapTracker::from_parts(
+        None,
+        vec![bind("", "q"), bind("resize", "l"), bind("resize", "h")],
+    );
+    let keys = |tracker: &SubmapTracker| -> Vec<String> {
+        tracker.live_binds().map(|b| b.key.clone()).collect()
+    };
+    assert_eq!(keys(&tracker), ["q"]);
+    tracker.set_submap("resize");
+    assert_eq!(tracker.submap(), Some("resize"));
+    assert_eq!(keys(&tracker), ["l", "h"]);
+    tracker.set_submap("");
+    assert_eq!(tracker.submap(), None);
+    assert_eq!(keys(&tracker), ["q"]);
+}
+
+#[cfg(feature = "data")]
+#[test]
+fn test_owned_binding() {
+    use crate::data::Bind;
+    use binds::*;
+    let bind = Bind {
+        locked: true,
+        mouse: false,
+        release: false,
+        repeat: true,
+        modmask: 65,
+        submap: String::new(),
+        key: "Q".to_string(),
+        keycode: 0,
+        dispatcher: "movetoworkspace".to_string(),
+        arg: "special:scratch".to_string(),
+        description: String::new(),
+    };
+    let owned = OwnedBinding::from(&bind);
+    assert_eq!(owned.mods, [Mod::SUPER, Mod::SHIFT]);
+    assert_eq!(owned.flags, [Flag::l, Flag::e]);
+    assert!(matches!(
+        owned.dispatch_type(),
+        DispatchType::MoveToWorkspace(
+            crate::dispatch::WorkspaceIdentifierWithSpecial::Special(Some("scratch")),
+            None
+        )
+    ));
+    let built = match Binder::gen_str(owned.as_binding()) {
+        Ok(v) => v,
+        Err(e) => panic!("Error occured: {e}"), // Note to greppers: this is in a test!
+    };
+    assert_eq!(built, "SUPERSHIFT,Q,movetoworkspace,special:scratch");
+
+    let desired = Binding {
+        mods: &[Mod::SHIFT, Mod::SUPER],
+        key: Key::Key("Q"),
+        flags: &[Flag::e, Flag::l],
+        dispatcher: DispatchType::Custom("movetoworkspace", "special:scratch"),
+        description: None,
+    };
+    assert_eq!(OwnedBinding::try_from(&desired).ok(), Some(owned));
+
+    // `bindd` binds keep their description when registered again
+    let described = Bind {
+        description: "Open a terminal".to_string(),
+        dispatcher: "exec".to_string(),
+        arg: "kitty".to_string(),
+        ..bind
+    };
+    let owned = OwnedBinding::from(&described);
+    assert_eq!(owned.flags, [Flag::l, Flag::e, Flag::d]);
+    let binding = owned.as_binding();
+    assert_eq!(binding.description, Some("Open a terminal"));
+    assert_eq!(
+        Binder::gen_str(binding.clone()).ok().as_deref(),
+        Some("SUPERSHIFT,Q,Open a terminal,exec,kitty")
+    );
+    assert_eq!(OwnedBinding::try_from(&binding).ok(), Some(owned));
+}
+
+#[cfg(feature = "data")]
+#[test]
+fn test_conflicts() {
+    use crate::data::Bind;
+    use binds::*;
+    let bind = |modmask: u16, key: &str, submap: &str, release: bool| Bind {
+        locked: false,
+        mouse: false,
+        release,
+        repeat: false,
+        modmask,
+        submap: submap.to_string(),
+        key: key.to_string(),
+        keycode: 0,
+        dispatcher: "exec".to_string(),
+        arg: String::new(),
+        description: String::new(),
+    };
+    let binds = [
+        bind(64, "Return", "", false),
+        bind(65, "Return", "", false),
+        bind(64, "return", "resize", false),
+        bind(64, "Return", "", true),
+        bind(0, "Return", "", false),
+    ];
+    let binding = |flags| Binding {
+        mods: &[Mod::SUPER],
+        key: Key::Key("RETURN"),
+        flags,
+        dispatcher: DispatchType::Exec("kitty"),
+        description: None,
+    };
+    let keys = |binding: &Binding, submap| -> Vec<(u16, bool)> {
+        Binder::conflicts(binding, submap, &binds)
+            .into_iter()
+            .map(|b| (b.modmask, b.release))
+            .collect()
+    };
+    assert_eq!(keys(&binding(&[]), None), [(64, false)]);
+    assert_eq!(keys(&binding(&[]), Some("resize")), [(64, false)]);
+    assert_eq!(keys(&binding(&[Flag::r]), None), [(64, true)]);
+    assert_eq!(
+        keys(&binding(&[Flag::i]), None),
+        [(64, false), (65, false), (0, false)]
+    );
+    assert!(keys(&binding(&[Flag::t]), None).is_empty());
+    assert!(keys(&binding(&[Flag::m]), None).is_empty());
+}
+
+#[test]
+fn test_keysym_binding() {
+    use binds::*;
+    use keysym::MouseButton;
+    let binding = Binding {
+        mods: &[Mod::SUPER],
+        key: Keysym::Mouse(MouseButton::Left).into(),
+        flags: &[Flag::m],
+        dispatcher: DispatchType::Custom("movewindow", ""),
+        description: None,
+    };
+    let built_bind = match Binder::gen_str(binding) {
+        Ok(v) => v,
+        Err(e) => panic!("Error occured: {e}"), // Note to greppers: this is in a test!
+    };
+    assert_eq!(built_bind, "SUPER,mouse:272,movewindow");
+}
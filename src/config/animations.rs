@@ -0,0 +1,391 @@
+use crate::data::{Animation, AnimationStyle, Animations, Bezier, BezierIdent};
+use crate::default_instance;
+use crate::error::HyprError;
+use crate::instance::Instance;
+use crate::keyword::{Keyword, OptionValue};
+
+/// The family of an animation, which decides what styles it accepts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AnimationKind {
+    Windows,
+    Layers,
+    Workspaces,
+    BorderAngle,
+    Other,
+}
+
+/// The animation tree of Hyprland
+const ANIMATION_TREE: &[(&str, AnimationKind)] = &[
+    ("global", AnimationKind::Other),
+    ("windows", AnimationKind::Windows),
+    ("windowsIn", AnimationKind::Windows),
+    ("windowsOut", AnimationKind::Windows),
+    ("windowsMove", AnimationKind::Windows),
+    ("layers", AnimationKind::Layers),
+    ("layersIn", AnimationKind::Layers),
+    ("layersOut", AnimationKind::Layers),
+    ("fade", AnimationKind::Other),
+    ("fadeIn", AnimationKind::Other),
+    ("fadeOut", AnimationKind::Other),
+    ("fadeSwitch", AnimationKind::Other),
+    ("fadeShadow", AnimationKind::Other),
+    ("fadeDim", AnimationKind::Other),
+    ("fadeLayers", AnimationKind::Other),
+    ("fadeLayersIn", AnimationKind::Other),
+    ("fadeLayersOut", AnimationKind::Other),
+    ("fadePopups", AnimationKind::Other),
+    ("fadePopupsIn", AnimationKind::Other),
+    ("fadePopupsOut", AnimationKind::Other),
+    ("fadeDpms", AnimationKind::Other),
+    ("border", AnimationKind::Other),
+    ("borderangle", AnimationKind::BorderAngle),
+    ("workspaces", AnimationKind::Workspaces),
+    ("workspacesIn", AnimationKind::Workspaces),
+    ("workspacesOut", AnimationKind::Workspaces),
+    ("specialWorkspace", AnimationKind::Workspaces),
+    ("specialWorkspaceIn", AnimationKind::Workspaces),
+    ("specialWorkspaceOut", AnimationKind::Workspaces),
+    ("zoomFactor", AnimationKind::Other),
+    ("monitorAdded", AnimationKind::Other),
+];
+
+impl AnimationKind {
+    fn of(name: &str) -> Option<Self> {
+        ANIMATION_TREE
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, kind)| *kind)
+    }
+
+    fn allows(self, style: &AnimationStyle) -> bool {
+        use AnimationStyle as S;
+        match style {
+            S::None | S::Unknown(_) => true,
+            S::Slide => matches!(self, Self::Windows | Self::Layers | Self::Workspaces),
+            S::PopIn(_) => matches!(self, Self::Windows | Self::Layers),
+            S::Fade => matches!(self, Self::Layers | Self::Workspaces),
+            S::SlideVert | S::SlideFade | S::SlideFadeVert => self == Self::Workspaces,
+            S::Once | S::Loop => self == Self::BorderAngle,
+        }
+    }
+}
+
+/// An animation (`animation`) config builder
+///
+/// ```rust
+/// use hyprland::config::animations::*;
+/// use hyprland::data::AnimationStyle;
+/// let anim = AnimationConfig::new("windows", 4.0)
+///     .bezier("overshot")
+///     .style(AnimationStyle::PopIn(80));
+/// assert_eq!(anim.to_string(), "animation = windows,1,4,overshot,popin 80%");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnimationConfig {
+    /// The name of the animation in the tree
+    pub name: String,
+    /// If the animation is enabled
+    pub enabled: bool,
+    /// The speed, in deciseconds
+    pub speed: f32,
+    /// The bezier curve to use
+    pub bezier: BezierIdent,
+    /// The style of the animation
+    pub style: AnimationStyle,
+}
+
+impl AnimationConfig {
+    /// Creates a new enabled animation using the default bezier
+    pub fn new<Str: Into<String>>(name: Str, speed: f32) -> Self {
+        Self {
+            name: name.into(),
+            enabled: true,
+            speed,
+            bezier: BezierIdent::Default,
+            style: AnimationStyle::None,
+        }
+    }
+
+    /// Creates a config disabling an animation
+    pub fn disabled<Str: Into<String>>(name: Str) -> Self {
+        Self {
+            enabled: false,
+            ..Self::new(name, 1.0)
+        }
+    }
+
+    /// Sets the bezier curve by name
+    pub fn bezier<Str: Into<String>>(mut self, name: Str) -> Self {
+        self.bezier = name.into().into();
+        self
+    }
+
+    /// Sets the style
+    pub fn style(mut self, style: AnimationStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Checks the name against the animation tree and the style against the animation kind
+    pub fn validate(&self) -> crate::Result<()> {
+        let Some(kind) = AnimationKind::of(&self.name) else {
+            return Err(HyprError::InvalidConfig(format!(
+                "unknown animation {}",
+                self.name
+            )));
+        };
+        if !self.enabled {
+            return Ok(());
+        }
+        if self.speed <= 0.0 {
+            return Err(HyprError::InvalidConfig(format!(
+                "invalid animation speed {}",
+                self.speed
+            )));
+        }
+        if !kind.allows(&self.style) {
+            return Err(HyprError::InvalidConfig(format!(
+                "animation {} does not support the {} style",
+                self.name, self.style
+            )));
+        }
+        Ok(())
+    }
+
+    /// Checks the config against a tree fetched from Hyprland, including the bezier name
+    pub fn validate_with(&self, tree: &Animations) -> crate::Result<()> {
+        if !tree.0.iter().any(|anim| anim.name == self.name) {
+            return Err(HyprError::InvalidConfig(format!(
+                "unknown animation {}",
+                self.name
+            )));
+        }
+        match &self.bezier {
+            BezierIdent::Specified(name) if !tree.1.contains(&self.bezier) => {
+                return Err(HyprError::InvalidConfig(format!("unknown bezier {name}")));
+            }
+            _ => {}
+        }
+        match AnimationKind::of(&self.name) {
+            // animations newer than our tree are only checked by Hyprland
+            None => Ok(()),
+            Some(_) => self.validate(),
+        }
+    }
+
+    /// Returns the value passed to the `animation` keyword
+    pub fn value(&self) -> crate::Result<String> {
+        self.validate()?;
+        Ok(self.gen_value())
+    }
+
+    fn gen_value(&self) -> String {
+        if !self.enabled {
+            return format!("{},0", self.name);
+        }
+        let bezier = match &self.bezier {
+            BezierIdent::Specified(name) => name.as_str(),
+            BezierIdent::None | BezierIdent::Default => "default",
+        };
+        let mut value = format!("{},1,{},{bezier}", self.name, self.speed);
+        if self.style != AnimationStyle::None {
+            value.push_str(&format!(",{}", self.style));
+        }
+        value
+    }
+
+    /// Returns the config as a line for a Hyprland config file
+    pub fn to_config_line(&self) -> String {
+        format!("animation = {}", self.gen_value())
+    }
+
+    /// Applies the animation at runtime
+    pub fn apply(&self) -> crate::Result<()> {
+        self.instance_apply(default_instance()?)
+    }
+
+    /// Applies the animation at runtime
+    pub fn instance_apply(&self, instance: &Instance) -> crate::Result<()> {
+        Keyword::instance_set(instance, "animation", OptionValue::String(self.value()?))
+    }
+
+    /// Applies the animation at runtime (async)
+    #[cfg(any(feature = "async-lite", feature = "tokio"))]
+    pub async fn apply_async(&self) -> crate::Result<()> {
+        self.instance_apply_async(default_instance()?).await
+    }
+
+    /// Applies the animation at runtime (async)
+    #[cfg(any(feature = "async-lite", feature = "tokio"))]
+    pub async fn instance_apply_async(&self, instance: &Instance) -> crate::Result<()> {
+        Keyword::instance_set_async(instance, "animation", OptionValue::String(self.value()?)).await
+    }
+}
+
+impl std::fmt::Display for AnimationConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_config_line())
+    }
+}
+
+impl From<&Animation> for AnimationConfig {
+    fn from(anim: &Animation) -> Self {
+        Self {
+            name: anim.name.clone(),
+            enabled: anim.enabled,
+            speed: anim.speed,
+            bezier: anim.bezier.clone(),
+            style: anim.style.clone(),
+        }
+    }
+}
+
+impl Bezier {
+    /// Creates a new bezier curve
+    pub fn new<Str: Into<String>>(name: Str, x0: f32, y0: f32, x1: f32, y1: f32) -> Self {
+        Self {
+            name: name.into(),
+            x0,
+            y0,
+            x1,
+            y1,
+        }
+    }
+
+    /// Returns the value passed to the `bezier` keyword
+    pub fn value(&self) -> crate::Result<String> {
+        if self.name.is_empty() || self.name.contains(',') {
+            return Err(HyprError::InvalidConfig(format!(
+                "invalid bezier name {:?}",
+                self.name
+            )));
+        }
+        if !(0.0..=1.0).contains(&self.x0) || !(0.0..=1.0).contains(&self.x1) {
+            return Err(HyprError::InvalidConfig(format!(
+                "bezier {} has x values outside of 0-1",
+                self.name
+            )));
+        }
+        Ok(self.gen_value())
+    }
+
+    fn gen_value(&self) -> String {
+        format!(
+            "{},{},{},{},{}",
+            self.name, self.x0, self.y0, self.x1, self.y1
+        )
+    }
+
+    /// Returns the bezier as a line for a Hyprland config file
+    pub fn to_config_line(&self) -> String {
+        format!("bezier = {}", self.gen_value())
+    }
+
+    /// Registers the bezier at runtime
+    pub fn apply(&self) -> crate::Result<()> {
+        self.instance_apply(default_instance()?)
+    }
+
+    /// Registers the bezier at runtime
+    pub fn instance_apply(&self, instance: &Instance) -> crate::Result<()> {
+        Keyword::instance_set(instance, "bezier", OptionValue::String(self.value()?))
+    }
+
+    /// Registers the bezier at runtime (async)
+    #[cfg(any(feature = "async-lite", feature = "tokio"))]
+    pub async fn apply_async(&self) -> crate::Result<()> {
+        self.instance_apply_async(default_instance()?).await
+    }
+
+    /// Registers the bezier at runtime (async)
+    #[cfg(any(feature = "async-lite", feature = "tokio"))]
+    pub async fn instance_apply_async(&self, instance: &Instance) -> crate::Result<()> {
+        Keyword::instance_set_async(instance, "bezier", OptionValue::String(self.value()?)).await
+    }
+}
+
+/// Exports an animation tree as config lines
+///
+/// Hyprland only reports bezier names, so beziers other than `default` have to be
+/// exported separately with [Bezier::to_config_line]
+pub fn tree_to_config_lines(tree: &Animations, only_overridden: bool) -> Vec<String> {
+    tree.0
+        .iter()
+        .filter(|anim| !only_overridden || anim.overridden)
+        .map(|anim| AnimationConfig::from(anim).to_config_line())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_animations() {
+        let anim = AnimationConfig::new("workspaces", 6.0)
+            .bezier("easeOut")
+            .style(AnimationStyle::SlideFadeVert);
+        assert_eq!(
+            anim.value().ok(),
+            Some("workspaces,1,6,easeOut,slidefadevert".to_string())
+        );
+        assert_eq!(
+            AnimationConfig::disabled("fadeDim").to_config_line(),
+            "animation = fadeDim,0"
+        );
+        assert_eq!(
+            AnimationConfig::new("border", 10.0).to_config_line(),
+            "animation = border,1,10,default"
+        );
+
+        let popin = AnimationConfig::new("windowsIn", 4.0).style(AnimationStyle::PopIn(80));
+        assert!(popin.validate().is_ok());
+        let popin = AnimationConfig::new("workspaces", 4.0).style(AnimationStyle::PopIn(80));
+        assert!(popin.validate().is_err());
+        let looped = AnimationConfig::new("borderangle", 50.0).style(AnimationStyle::Loop);
+        assert!(looped.validate().is_ok());
+        assert!(AnimationConfig::new("windowz", 4.0).validate().is_err());
+        assert!(AnimationConfig::new("windows", 0.0).validate().is_err());
+    }
+
+    #[test]
+    fn test_validate_with_tree() {
+        let tree = Animations(
+            vec![Animation {
+                name: "windows".to_string(),
+                overridden: true,
+                bezier: BezierIdent::Specified("overshot".to_string()),
+                enabled: true,
+                speed: 4.0,
+                style: AnimationStyle::PopIn(80),
+            }],
+            vec![
+                BezierIdent::Default,
+                BezierIdent::Specified("overshot".to_string()),
+            ],
+        );
+        assert_eq!(
+            tree_to_config_lines(&tree, true),
+            ["animation = windows,1,4,overshot,popin 80%"]
+        );
+        let anim = AnimationConfig::new("windows", 2.0).bezier("overshot");
+        assert!(anim.validate_with(&tree).is_ok());
+        assert!(anim.clone().bezier("linear").validate_with(&tree).is_err());
+        let anim = AnimationConfig::new("layers", 2.0);
+        assert!(anim.validate_with(&tree).is_err());
+    }
+
+    #[test]
+    fn test_beziers() {
+        let bezier = Bezier::new("overshot", 0.05, 0.9, 0.1, 1.1);
+        assert_eq!(
+            bezier.value().ok(),
+            Some("overshot,0.05,0.9,0.1,1.1".to_string())
+        );
+        assert_eq!(
+            bezier.to_config_line(),
+            "bezier = overshot,0.05,0.9,0.1,1.1"
+        );
+        assert!(Bezier::new("bad", 1.5, 0.0, 0.0, 1.0).value().is_err());
+    }
+}
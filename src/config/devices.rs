@@ -0,0 +1,360 @@
+use crate::data::{Keyboard, Mouse, Tablet, Transforms};
+use crate::default_instance;
+use crate::error::HyprError;
+use crate::instance::Instance;
+use crate::keyword::{Keyword, OptionValue};
+use derive_more::Display;
+
+/// The pointer acceleration profile of a device
+#[derive(Debug, Clone, PartialEq, Display)]
+pub enum AccelProfile {
+    /// No acceleration
+    #[display("flat")]
+    Flat,
+    /// Acceleration based on the pointer speed
+    #[display("adaptive")]
+    Adaptive,
+    /// A custom curve, given as `<step> <points...>`
+    #[display("custom {_0}")]
+    Custom(String),
+}
+
+/// A per-device input option
+#[derive(Debug, Clone)]
+pub enum DeviceOption {
+    /// Enables or disables the device
+    Enabled(bool),
+    /// The pointer sensitivity (-1 to 1)
+    Sensitivity(f64),
+    /// The pointer acceleration profile
+    AccelProfile(AccelProfile),
+    /// Inverts scrolling
+    NaturalScroll(bool),
+    /// Swaps the left and right buttons
+    LeftHanded(bool),
+    /// The XKB layouts of a keyboard
+    KbLayout(String),
+    /// The XKB variants of a keyboard
+    KbVariant(String),
+    /// The XKB model of a keyboard
+    KbModel(String),
+    /// The XKB options of a keyboard
+    KbOptions(String),
+    /// The XKB rules of a keyboard
+    KbRules(String),
+    /// The repeat rate of a keyboard, in repeats per second
+    RepeatRate(i64),
+    /// The delay before a key starts repeating, in milliseconds
+    RepeatDelay(i64),
+    /// The monitor a tablet is mapped to (`current` for the focused one)
+    Output(String),
+    /// The position of the mapped region of a tablet
+    RegionPosition(f64, f64),
+    /// The size of the mapped region of a tablet
+    RegionSize(f64, f64),
+    /// Makes a tablet act like a touchpad
+    RelativeInput(bool),
+    /// The transform of a tablet
+    Transform(Transforms),
+    /// The size of the active area of a tablet, in millimeters
+    ActiveAreaSize(f64, f64),
+    /// The position of the active area of a tablet, in millimeters
+    ActiveAreaPosition(f64, f64),
+    /// Lets you use options not supported by hyprland-rs yet
+    Custom(String, OptionValue),
+}
+
+impl DeviceOption {
+    /// Returns the name of the option
+    pub fn key(&self) -> &str {
+        match self {
+            Self::Enabled(_) => "enabled",
+            Self::Sensitivity(_) => "sensitivity",
+            Self::AccelProfile(_) => "accel_profile",
+            Self::NaturalScroll(_) => "natural_scroll",
+            Self::LeftHanded(_) => "left_handed",
+            Self::KbLayout(_) => "kb_layout",
+            Self::KbVariant(_) => "kb_variant",
+            Self::KbModel(_) => "kb_model",
+            Self::KbOptions(_) => "kb_options",
+            Self::KbRules(_) => "kb_rules",
+            Self::RepeatRate(_) => "repeat_rate",
+            Self::RepeatDelay(_) => "repeat_delay",
+            Self::Output(_) => "output",
+            Self::RegionPosition(..) => "region_position",
+            Self::RegionSize(..) => "region_size",
+            Self::RelativeInput(_) => "relative_input",
+            Self::Transform(_) => "transform",
+            Self::ActiveAreaSize(..) => "active_area_size",
+            Self::ActiveAreaPosition(..) => "active_area_position",
+            Self::Custom(key, _) => key,
+        }
+    }
+
+    /// Returns the value of the option
+    pub fn value(&self) -> OptionValue {
+        match self {
+            Self::Enabled(b)
+            | Self::NaturalScroll(b)
+            | Self::LeftHanded(b)
+            | Self::RelativeInput(b) => OptionValue::Bool(*b),
+            Self::Sensitivity(f) => OptionValue::Float(*f),
+            Self::AccelProfile(profile) => OptionValue::String(profile.to_string()),
+            Self::KbLayout(s)
+            | Self::KbVariant(s)
+            | Self::KbModel(s)
+            | Self::KbOptions(s)
+            | Self::KbRules(s)
+            | Self::Output(s) => OptionValue::String(s.clone()),
+            Self::RepeatRate(i) | Self::RepeatDelay(i) => OptionValue::Int(*i),
+            Self::RegionPosition(x, y)
+            | Self::RegionSize(x, y)
+            | Self::ActiveAreaSize(x, y)
+            | Self::ActiveAreaPosition(x, y) => OptionValue::FloatVec2([*x, *y]),
+            Self::Transform(transform) => OptionValue::Int(*transform as i64),
+            Self::Custom(_, value) => value.clone(),
+        }
+    }
+
+    fn validate(&self) -> crate::Result<()> {
+        match self {
+            Self::Sensitivity(f) if !(-1.0..=1.0).contains(f) => Err(HyprError::InvalidConfig(
+                format!("sensitivity {f} is outside of -1 to 1"),
+            )),
+            Self::RepeatRate(i) | Self::RepeatDelay(i) if *i < 0 => Err(HyprError::InvalidConfig(
+                format!("{} can't be negative", self.key()),
+            )),
+            Self::RegionSize(x, y) | Self::ActiveAreaSize(x, y) if *x < 0.0 || *y < 0.0 => Err(
+                HyprError::InvalidConfig(format!("{} can't be negative", self.key())),
+            ),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// A per-device input config builder, applied through `device[<name>]:<option>` keywords
+///
+/// ```rust
+/// use hyprland::config::devices::*;
+/// let mouse = DeviceConfig::new("logitech-g502")
+///     .sensitivity(-0.5)
+///     .accel_profile(AccelProfile::Flat);
+/// assert_eq!(
+///     mouse.to_config_lines()?,
+///     [
+///         "device {",
+///         "    name = logitech-g502",
+///         "    sensitivity = -0.5",
+///         "    accel_profile = flat",
+///         "}",
+///     ]
+/// );
+/// # Ok::<(), hyprland::error::HyprError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct DeviceConfig {
+    /// The name of the device, as listed by [Devices][crate::data::Devices]
+    pub name: String,
+    /// The options to set
+    pub options: Vec<DeviceOption>,
+}
+
+impl DeviceConfig {
+    /// Creates an empty config for a device
+    pub fn new<Str: Into<String>>(name: Str) -> Self {
+        Self {
+            name: name.into(),
+            options: vec![],
+        }
+    }
+
+    /// Adds an option
+    pub fn option(mut self, option: DeviceOption) -> Self {
+        self.options.push(option);
+        self
+    }
+
+    /// Sets the pointer sensitivity (-1 to 1)
+    pub fn sensitivity(self, sensitivity: f64) -> Self {
+        self.option(DeviceOption::Sensitivity(sensitivity))
+    }
+
+    /// Sets the pointer acceleration profile
+    pub fn accel_profile(self, profile: AccelProfile) -> Self {
+        self.option(DeviceOption::AccelProfile(profile))
+    }
+
+    /// Sets natural (inverted) scrolling
+    pub fn natural_scroll(self, natural_scroll: bool) -> Self {
+        self.option(DeviceOption::NaturalScroll(natural_scroll))
+    }
+
+    /// Sets the XKB layouts of a keyboard
+    pub fn kb_layout<Str: Into<String>>(self, layout: Str) -> Self {
+        self.option(DeviceOption::KbLayout(layout.into()))
+    }
+
+    /// Maps a tablet to a monitor
+    pub fn output<Str: Into<String>>(self, monitor: Str) -> Self {
+        self.option(DeviceOption::Output(monitor.into()))
+    }
+
+    /// Maps a tablet to a region of the layout
+    pub fn region(self, position: (f64, f64), size: (f64, f64)) -> Self {
+        self.option(DeviceOption::RegionPosition(position.0, position.1))
+            .option(DeviceOption::RegionSize(size.0, size.1))
+    }
+
+    fn validate(&self) -> crate::Result<()> {
+        if self.name.is_empty() || self.name.contains(['[', ']', '\n']) {
+            return Err(HyprError::InvalidConfig(format!(
+                "invalid device name {:?}",
+                self.name
+            )));
+        }
+        self.options.iter().try_for_each(DeviceOption::validate)
+    }
+
+    /// Returns the keywords and values to set
+    pub fn keywords(&self) -> crate::Result<Vec<(String, OptionValue)>> {
+        self.validate()?;
+        Ok(self
+            .options
+            .iter()
+            .map(|option| {
+                (
+                    format!("device[{}]:{}", self.name, option.key()),
+                    option.value(),
+                )
+            })
+            .collect())
+    }
+
+    /// Returns the config as a `device` block for a Hyprland config file
+    pub fn to_config_lines(&self) -> crate::Result<Vec<String>> {
+        self.validate()?;
+        let mut lines = vec!["device {".to_string(), format!("    name = {}", self.name)];
+        lines.extend(
+            self.options
+                .iter()
+                .map(|option| format!("    {} = {}", option.key(), option.value())),
+        );
+        lines.push("}".to_string());
+        Ok(lines)
+    }
+
+    /// Applies the config at runtime
+    pub fn apply(&self) -> crate::Result<()> {
+        self.instance_apply(default_instance()?)
+    }
+
+    /// Applies the config at runtime
+    pub fn instance_apply(&self, instance: &Instance) -> crate::Result<()> {
+        for (key, value) in self.keywords()? {
+            Keyword::instance_set(instance, key, value)?;
+        }
+        Ok(())
+    }
+
+    /// Applies the config at runtime (async)
+    #[cfg(any(feature = "async-lite", feature = "tokio"))]
+    pub async fn apply_async(&self) -> crate::Result<()> {
+        self.instance_apply_async(default_instance()?).await
+    }
+
+    /// Applies the config at runtime (async)
+    #[cfg(any(feature = "async-lite", feature = "tokio"))]
+    pub async fn instance_apply_async(&self, instance: &Instance) -> crate::Result<()> {
+        for (key, value) in self.keywords()? {
+            Keyword::instance_set_async(instance, key, value).await?;
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for DeviceConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let lines = self.to_config_lines().map_err(|_| std::fmt::Error)?;
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+impl From<&Mouse> for DeviceConfig {
+    fn from(mouse: &Mouse) -> Self {
+        Self::new(mouse.name.clone())
+    }
+}
+
+impl From<&Keyboard> for DeviceConfig {
+    fn from(keyboard: &Keyboard) -> Self {
+        Self::new(keyboard.name.clone())
+    }
+}
+
+impl TryFrom<&Tablet> for DeviceConfig {
+    type Error = HyprError;
+
+    fn try_from(tablet: &Tablet) -> Result<Self, Self::Error> {
+        match &tablet.name {
+            Some(name) => Ok(Self::new(name.clone())),
+            None => Err(HyprError::InvalidConfig(format!(
+                "tablet {} has no name",
+                tablet.address
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_device_keywords() {
+        let tablet = Tablet {
+            address: crate::shared::Address::new("5d3a1f80"),
+            tablet_type: None,
+            belongs_to: None,
+            name: Some("wacom-intuos-s-pen".to_string()),
+        };
+        #[allow(clippy::unwrap_used)]
+        let config = DeviceConfig::try_from(&tablet)
+            .unwrap()
+            .output("DP-1")
+            .region((100.0, 0.0), (1920.0, 1080.0))
+            .option(DeviceOption::Transform(Transforms::Normal180));
+        #[allow(clippy::unwrap_used)]
+        let keywords: Vec<_> = config
+            .keywords()
+            .unwrap()
+            .into_iter()
+            .map(|(k, v)| format!("{k} {v}"))
+            .collect();
+        assert_eq!(
+            keywords,
+            [
+                "device[wacom-intuos-s-pen]:output DP-1",
+                "device[wacom-intuos-s-pen]:region_position 100 0",
+                "device[wacom-intuos-s-pen]:region_size 1920 1080",
+                "device[wacom-intuos-s-pen]:transform 2",
+            ]
+        );
+
+        let keyboard = DeviceConfig::new("at-translated-set-2-keyboard")
+            .kb_layout("us,de")
+            .natural_scroll(true);
+        #[allow(clippy::unwrap_used)]
+        let lines = keyboard.to_config_lines().unwrap();
+        assert_eq!(
+            lines[2..4],
+            ["    kb_layout = us,de", "    natural_scroll = true"]
+        );
+
+        let too_fast = DeviceConfig::new("mouse").sensitivity(1.5);
+        assert!(too_fast.keywords().is_err());
+        assert!(too_fast.to_config_lines().is_err());
+        assert!(DeviceConfig::new("").sensitivity(0.5).keywords().is_err());
+        let bracket = DeviceConfig::new("mouse]").sensitivity(0.5);
+        assert!(bracket.to_config_lines().is_err());
+    }
+}
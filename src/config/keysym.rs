@@ -0,0 +1,372 @@
+use crate::error::HyprError;
+use derive_more::Display;
+use std::str::FromStr;
+
+/// XKB key names known by hyprland-rs, other than letters, digits and function keys
+const NAMED_KEYS: &[&str] = &[
+    "Return",
+    "Escape",
+    "Tab",
+    "space",
+    "BackSpace",
+    "Delete",
+    "Insert",
+    "Home",
+    "End",
+    "Prior",
+    "Next",
+    "Page_Up",
+    "Page_Down",
+    "Left",
+    "Right",
+    "Up",
+    "Down",
+    "Print",
+    "Pause",
+    "Scroll_Lock",
+    "Caps_Lock",
+    "Num_Lock",
+    "Menu",
+    "Super_L",
+    "Super_R",
+    "Shift_L",
+    "Shift_R",
+    "Control_L",
+    "Control_R",
+    "Alt_L",
+    "Alt_R",
+    "comma",
+    "period",
+    "slash",
+    "backslash",
+    "semicolon",
+    "colon",
+    "apostrophe",
+    "quotedbl",
+    "grave",
+    "asciitilde",
+    "minus",
+    "underscore",
+    "equal",
+    "plus",
+    "bracketleft",
+    "bracketright",
+    "braceleft",
+    "braceright",
+    "parenleft",
+    "parenright",
+    "less",
+    "greater",
+    "question",
+    "exclam",
+    "at",
+    "numbersign",
+    "dollar",
+    "percent",
+    "asciicircum",
+    "ampersand",
+    "asterisk",
+    "bar",
+    "KP_0",
+    "KP_1",
+    "KP_2",
+    "KP_3",
+    "KP_4",
+    "KP_5",
+    "KP_6",
+    "KP_7",
+    "KP_8",
+    "KP_9",
+    "KP_Enter",
+    "KP_Add",
+    "KP_Subtract",
+    "KP_Multiply",
+    "KP_Divide",
+    "KP_Decimal",
+    "XF86AudioRaiseVolume",
+    "XF86AudioLowerVolume",
+    "XF86AudioMute",
+    "XF86AudioMicMute",
+    "XF86AudioPlay",
+    "XF86AudioPause",
+    "XF86AudioStop",
+    "XF86AudioNext",
+    "XF86AudioPrev",
+    "XF86AudioMedia",
+    "XF86AudioRecord",
+    "XF86MonBrightnessUp",
+    "XF86MonBrightnessDown",
+    "XF86KbdBrightnessUp",
+    "XF86KbdBrightnessDown",
+    "XF86KbdLightOnOff",
+    "XF86Calculator",
+    "XF86Mail",
+    "XF86Search",
+    "XF86Explorer",
+    "XF86WWW",
+    "XF86HomePage",
+    "XF86Favorites",
+    "XF86Tools",
+    "XF86Back",
+    "XF86Forward",
+    "XF86Refresh",
+    "XF86Copy",
+    "XF86Cut",
+    "XF86Paste",
+    "XF86Eject",
+    "XF86PowerOff",
+    "XF86Sleep",
+    "XF86Suspend",
+    "XF86WakeUp",
+    "XF86ScreenSaver",
+    "XF86Display",
+    "XF86TouchpadToggle",
+    "XF86WLAN",
+    "XF86RFKill",
+    "XF86Bluetooth",
+];
+
+/// A mouse button, as used in `mouse:<code>` keys
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MouseButton {
+    /// The left button (272)
+    Left,
+    /// The right button (273)
+    Right,
+    /// The middle button (274)
+    Middle,
+    /// The side button (275)
+    Side,
+    /// The extra button (276)
+    Extra,
+    /// The forward button (277)
+    Forward,
+    /// The back button (278)
+    Back,
+    /// Any other button, by its linux event code
+    Other(u16),
+}
+
+impl MouseButton {
+    /// The linux event code of the button
+    pub fn code(self) -> u16 {
+        match self {
+            MouseButton::Left => 272,
+            MouseButton::Right => 273,
+            MouseButton::Middle => 274,
+            MouseButton::Side => 275,
+            MouseButton::Extra => 276,
+            MouseButton::Forward => 277,
+            MouseButton::Back => 278,
+            MouseButton::Other(code) => code,
+        }
+    }
+
+    /// Gets a button from its linux event code
+    pub fn from_code(code: u16) -> Self {
+        match code {
+            272 => MouseButton::Left,
+            273 => MouseButton::Right,
+            274 => MouseButton::Middle,
+            275 => MouseButton::Side,
+            276 => MouseButton::Extra,
+            277 => MouseButton::Forward,
+            278 => MouseButton::Back,
+            code => MouseButton::Other(code),
+        }
+    }
+}
+
+/// A direction of the mouse wheel
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display)]
+pub enum WheelDirection {
+    /// Scrolling up
+    #[display("mouse_up")]
+    Up,
+    /// Scrolling down
+    #[display("mouse_down")]
+    Down,
+    /// Scrolling left
+    #[display("mouse_left")]
+    Left,
+    /// Scrolling right
+    #[display("mouse_right")]
+    Right,
+}
+
+/// A key that can be used in a bind, in Hyprland's bind syntax
+///
+/// Parsing is case insensitive like Hyprland, and rejects XKB names hyprland-rs doesn't know
+/// about. Use [Keysym::Other] for those
+///
+/// ```rust
+/// use hyprland::config::keysym::*;
+/// let key: Keysym = "xf86audioraisevolume".parse()?;
+/// assert_eq!(key.to_string(), "XF86AudioRaiseVolume");
+/// assert_eq!(Keysym::Mouse(MouseButton::Left).to_string(), "mouse:272");
+/// assert!("Retrun".parse::<Keysym>().is_err());
+/// # Ok::<(), hyprland::error::HyprError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Display)]
+pub enum Keysym {
+    /// A letter key, stored lowercase
+    #[display("{_0}")]
+    Letter(char),
+    /// A digit key (0-9)
+    #[display("{_0}")]
+    Digit(u8),
+    /// A function key (F1-F35)
+    #[display("F{_0}")]
+    Function(u8),
+    /// A named XKB key, such as `Return` or `XF86AudioMute`
+    #[display("{_0}")]
+    Named(&'static str),
+    /// A raw keycode
+    #[display("code:{_0}")]
+    Code(u32),
+    /// A mouse button
+    #[display("mouse:{}", _0.code())]
+    Mouse(MouseButton),
+    /// A direction of the mouse wheel
+    #[display("{_0}")]
+    Wheel(WheelDirection),
+    /// A switch, such as a laptop lid, optionally only when toggled on or off
+    #[display("switch:{}{name}", state.map(|on| if on { "on:" } else { "off:" }).unwrap_or_default())]
+    Switch {
+        /// The name of the switch
+        name: String,
+        /// [Some] to only trigger when turned on or off
+        state: Option<bool>,
+    },
+    /// Any other XKB key name, passed to Hyprland unchecked
+    #[display("{_0}")]
+    Other(String),
+}
+
+impl Keysym {
+    /// Creates a named key, failing if hyprland-rs doesn't know it
+    pub fn named(name: &str) -> crate::Result<Self> {
+        NAMED_KEYS
+            .iter()
+            .find(|known| known.eq_ignore_ascii_case(name))
+            .map(|known| Keysym::Named(known))
+            .ok_or_else(|| HyprError::InvalidConfig(format!("unknown key {name:?}")))
+    }
+}
+
+impl FromStr for Keysym {
+    type Err = HyprError;
+
+    fn from_str(s: &str) -> crate::Result<Self> {
+        let err = || HyprError::InvalidConfig(format!("unknown key {s:?}"));
+        let mut chars = s.chars();
+        if let (Some(c), None) = (chars.next(), chars.next()) {
+            return match c {
+                'a'..='z' | 'A'..='Z' => Ok(Keysym::Letter(c.to_ascii_lowercase())),
+                '0'..='9' => Ok(Keysym::Digit(c as u8 - b'0')),
+                _ => Err(err()),
+            };
+        }
+        if let Some(code) = s.strip_prefix("code:") {
+            return code.parse().map(Keysym::Code).map_err(|_| err());
+        }
+        if let Some(code) = s.strip_prefix("mouse:") {
+            return code
+                .parse()
+                .map(|code| Keysym::Mouse(MouseButton::from_code(code)))
+                .map_err(|_| err());
+        }
+        if let Some(switch) = s.strip_prefix("switch:") {
+            let (name, state) = if let Some(name) = switch.strip_prefix("on:") {
+                (name, Some(true))
+            } else if let Some(name) = switch.strip_prefix("off:") {
+                (name, Some(false))
+            } else {
+                (switch, None)
+            };
+            if name.is_empty() {
+                return Err(err());
+            }
+            return Ok(Keysym::Switch {
+                name: name.to_string(),
+                state,
+            });
+        }
+        match s {
+            "mouse_up" => return Ok(Keysym::Wheel(WheelDirection::Up)),
+            "mouse_down" => return Ok(Keysym::Wheel(WheelDirection::Down)),
+            "mouse_left" => return Ok(Keysym::Wheel(WheelDirection::Left)),
+            "mouse_right" => return Ok(Keysym::Wheel(WheelDirection::Right)),
+            _ => {}
+        }
+        if let Some(Ok(n)) = s.strip_prefix(['F', 'f']).map(str::parse::<u8>) {
+            return match n {
+                1..=35 => Ok(Keysym::Function(n)),
+                _ => Err(err()),
+            };
+        }
+        Keysym::named(s)
+    }
+}
+
+impl TryFrom<&str> for Keysym {
+    type Error = HyprError;
+
+    fn try_from(s: &str) -> crate::Result<Self> {
+        s.parse()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[track_caller]
+    fn check(input: &str, expected: Keysym, display: &str) {
+        #[allow(clippy::unwrap_used)]
+        let parsed: Keysym = input.parse().unwrap();
+        assert_eq!(parsed, expected);
+        assert_eq!(parsed.to_string(), display);
+        #[allow(clippy::unwrap_used)]
+        let reparsed: Keysym = display.parse().unwrap();
+        assert_eq!(reparsed, expected);
+    }
+
+    #[test]
+    fn test_keysyms() {
+        check("Q", Keysym::Letter('q'), "q");
+        check("7", Keysym::Digit(7), "7");
+        check("f12", Keysym::Function(12), "F12");
+        check("return", Keysym::Named("Return"), "Return");
+        check(
+            "XF86AudioMute",
+            Keysym::Named("XF86AudioMute"),
+            "XF86AudioMute",
+        );
+        check("code:24", Keysym::Code(24), "code:24");
+        check("mouse:273", Keysym::Mouse(MouseButton::Right), "mouse:273");
+        check(
+            "mouse:290",
+            Keysym::Mouse(MouseButton::Other(290)),
+            "mouse:290",
+        );
+        check(
+            "mouse_down",
+            Keysym::Wheel(WheelDirection::Down),
+            "mouse_down",
+        );
+        check(
+            "switch:on:Lid Switch",
+            Keysym::Switch {
+                name: "Lid Switch".to_string(),
+                state: Some(true),
+            },
+            "switch:on:Lid Switch",
+        );
+
+        assert!("Retrun".parse::<Keysym>().is_err());
+        assert!("F36".parse::<Keysym>().is_err());
+        assert!("code:abc".parse::<Keysym>().is_err());
+        assert!("switch:".parse::<Keysym>().is_err());
+    }
+}
@@ -0,0 +1,276 @@
+use crate::default_instance;
+use crate::error::HyprError;
+use crate::instance::Instance;
+use crate::keyword::{Keyword, OptionValue};
+use crate::shared::Address;
+use derive_more::Display;
+
+/// The action a layer rule applies to the layer surfaces it matches
+#[derive(Debug, Clone, PartialEq, Display)]
+pub enum LayerRuleAction {
+    /// Blurs the layer surface
+    #[display("blur")]
+    Blur,
+    /// Blurs the popups of the layer surface
+    #[display("blurpopups")]
+    BlurPopups,
+    /// Ignores pixels with an alpha below the threshold (0-1) when blurring
+    #[display("ignorealpha {_0}")]
+    IgnoreAlpha(f32),
+    /// Ignores fully transparent pixels when blurring
+    #[display("ignorezero")]
+    IgnoreZero,
+    /// Dims everything behind the layer surface
+    #[display("dimaround")]
+    DimAround,
+    /// Sets the blur xray mode for the layer surface
+    #[display("xray {}", *_0 as u8)]
+    Xray(bool),
+    /// Forces an animation style for the layer surface
+    #[display("animation {_0}")]
+    Animation(String),
+    /// Disables animations for the layer surface
+    #[display("noanim")]
+    NoAnim,
+    /// Sets the order relative to other layer surfaces, lower is rendered first
+    #[display("order {_0}")]
+    Order(i32),
+    /// Renders the layer surface above the lockscreen
+    #[display("abovelock")]
+    AboveLock,
+    /// Removes all the layer rules previously set for the target
+    #[display("unset")]
+    Unset,
+    /// Lets you use rules not supported by hyprland-rs yet
+    #[display("{_0}")]
+    Custom(String),
+}
+
+/// The layer surfaces a rule applies to
+#[derive(Debug, Clone, PartialEq, Eq, Display)]
+pub enum LayerRuleTarget {
+    /// A Regular Expression to match the layer namespace
+    #[display("{_0}")]
+    Namespace(String),
+    /// The address of a layer surface
+    #[display("address:{_0}")]
+    Address(Address),
+}
+
+/// Matches exactly the namespace of the layer surface
+#[cfg(feature = "data")]
+impl From<&crate::data::LayerClient> for LayerRuleTarget {
+    fn from(layer: &crate::data::LayerClient) -> Self {
+        Self::Namespace(format!("^({})$", escape_regex(&layer.namespace)))
+    }
+}
+
+/// Escapes the regex metacharacters so the string is matched literally
+#[cfg(feature = "data")]
+fn escape_regex(string: &str) -> String {
+    let mut escaped = String::with_capacity(string.len());
+    for c in string.chars() {
+        if matches!(
+            c,
+            '\\' | '.' | '+' | '*' | '?' | '(' | ')' | '|' | '[' | ']' | '{' | '}' | '^' | '$'
+        ) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// A layer rule (`layerrule`) builder
+///
+/// Every action generates its own `layerrule` line, all sharing the same target
+///
+/// ```rust
+/// use hyprland::config::layer_rules::*;
+/// let rule = LayerRule::new(LayerRuleAction::Blur, LayerRuleTarget::Namespace("waybar".into()))
+///     .action(LayerRuleAction::IgnoreZero);
+/// assert_eq!(rule.to_string(), "layerrule = blur, waybar\nlayerrule = ignorezero, waybar");
+/// ```
+#[derive(Debug, Clone)]
+pub struct LayerRule {
+    /// The actions to apply
+    pub actions: Vec<LayerRuleAction>,
+    /// The layer surfaces to apply them to
+    pub target: LayerRuleTarget,
+}
+
+impl LayerRule {
+    /// Creates a new layer rule with a single action
+    pub fn new(action: LayerRuleAction, target: LayerRuleTarget) -> Self {
+        Self {
+            actions: vec![action],
+            target,
+        }
+    }
+
+    /// Adds another action to the rule
+    pub fn action(mut self, action: LayerRuleAction) -> Self {
+        self.actions.push(action);
+        self
+    }
+
+    /// Returns the values passed to the `layerrule` keyword, one per action
+    pub fn values(&self) -> crate::Result<Vec<String>> {
+        if self.actions.is_empty() {
+            return Err(HyprError::InvalidConfig(
+                "a layer rule needs at least one action".to_string(),
+            ));
+        }
+        if matches!(&self.target, LayerRuleTarget::Namespace(namespace) if namespace.is_empty()) {
+            return Err(HyprError::InvalidConfig(
+                "a layer rule needs a namespace".to_string(),
+            ));
+        }
+        Ok(self
+            .actions
+            .iter()
+            .map(|a| format!("{a}, {}", self.target))
+            .collect())
+    }
+
+    /// Returns the rule as lines for a Hyprland config file
+    pub fn to_config_lines(&self) -> crate::Result<Vec<String>> {
+        Ok(self
+            .values()?
+            .into_iter()
+            .map(|value| format!("layerrule = {value}"))
+            .collect())
+    }
+
+    /// Applies the rule at runtime
+    pub fn apply(&self) -> crate::Result<()> {
+        self.instance_apply(default_instance()?)
+    }
+
+    /// Applies the rule at runtime
+    pub fn instance_apply(&self, instance: &Instance) -> crate::Result<()> {
+        for value in self.values()? {
+            Keyword::instance_set(instance, "layerrule", OptionValue::String(value))?;
+        }
+        Ok(())
+    }
+
+    /// Applies the rule at runtime (async)
+    #[cfg(any(feature = "async-lite", feature = "tokio"))]
+    pub async fn apply_async(&self) -> crate::Result<()> {
+        self.instance_apply_async(default_instance()?).await
+    }
+
+    /// Applies the rule at runtime (async)
+    #[cfg(any(feature = "async-lite", feature = "tokio"))]
+    pub async fn instance_apply_async(&self, instance: &Instance) -> crate::Result<()> {
+        for value in self.values()? {
+            Keyword::instance_set_async(instance, "layerrule", OptionValue::String(value)).await?;
+        }
+        Ok(())
+    }
+
+    /// Returns the namespaces of all the current layer surfaces, sorted and deduplicated
+    #[cfg(feature = "data")]
+    pub fn namespaces() -> crate::Result<Vec<String>> {
+        Self::instance_namespaces(default_instance()?)
+    }
+
+    /// Returns the namespaces of all the current layer surfaces, sorted and deduplicated
+    #[cfg(feature = "data")]
+    pub fn instance_namespaces(instance: &Instance) -> crate::Result<Vec<String>> {
+        use crate::shared::HyprData;
+        Ok(collect_namespaces(crate::data::Layers::instance_get(
+            instance,
+        )?))
+    }
+
+    /// Returns the namespaces of all the current layer surfaces, sorted and deduplicated (async)
+    #[cfg(all(feature = "data", any(feature = "async-lite", feature = "tokio")))]
+    pub async fn namespaces_async() -> crate::Result<Vec<String>> {
+        Self::instance_namespaces_async(default_instance()?).await
+    }
+
+    /// Returns the namespaces of all the current layer surfaces, sorted and deduplicated (async)
+    #[cfg(all(feature = "data", any(feature = "async-lite", feature = "tokio")))]
+    pub async fn instance_namespaces_async(instance: &Instance) -> crate::Result<Vec<String>> {
+        use crate::shared::HyprData;
+        Ok(collect_namespaces(
+            crate::data::Layers::instance_get_async(instance).await?,
+        ))
+    }
+}
+
+#[cfg(feature = "data")]
+fn collect_namespaces(layers: crate::data::Layers) -> Vec<String> {
+    let mut namespaces: Vec<String> = layers
+        .into_values()
+        .flat_map(|display| display.into_values())
+        .flatten()
+        .map(|layer| layer.namespace)
+        .collect();
+    namespaces.sort();
+    namespaces.dedup();
+    namespaces
+}
+
+impl std::fmt::Display for LayerRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let lines = self.to_config_lines().map_err(|_| std::fmt::Error)?;
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_layer_rules() {
+        let rule = LayerRule::new(
+            LayerRuleAction::IgnoreAlpha(0.5),
+            LayerRuleTarget::Namespace("^(rofi)$".into()),
+        )
+        .action(LayerRuleAction::Animation("slide top".into()))
+        .action(LayerRuleAction::Order(-1))
+        .action(LayerRuleAction::Xray(true));
+        #[allow(clippy::unwrap_used)]
+        let lines = rule.to_config_lines().unwrap();
+        assert_eq!(
+            lines,
+            [
+                "layerrule = ignorealpha 0.5, ^(rofi)$",
+                "layerrule = animation slide top, ^(rofi)$",
+                "layerrule = order -1, ^(rofi)$",
+                "layerrule = xray 1, ^(rofi)$",
+            ]
+        );
+
+        let rule = LayerRule::new(
+            LayerRuleAction::NoAnim,
+            LayerRuleTarget::Address(Address::new("55d0f32c1ec0")),
+        );
+        #[allow(clippy::unwrap_used)]
+        let values = rule.values().unwrap();
+        assert_eq!(values, ["noanim, address:0x55d0f32c1ec0"]);
+
+        let rule = LayerRule::new(
+            LayerRuleAction::Blur,
+            LayerRuleTarget::Namespace(String::new()),
+        );
+        assert!(matches!(
+            rule.to_config_lines(),
+            Err(HyprError::InvalidConfig(_))
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "data")]
+    fn test_escape_regex() {
+        assert_eq!(escape_regex("waybar"), "waybar");
+        assert_eq!(
+            escape_regex("gtk-layer-shell.(1)+[x]$"),
+            r"gtk-layer-shell\.\(1\)\+\[x\]\$"
+        );
+    }
+}
@@ -0,0 +1,381 @@
+#[cfg(feature = "data")]
+use crate::data::Monitor;
+use crate::default_instance;
+use crate::error::HyprError;
+use crate::instance::Instance;
+use crate::keyword::{Keyword, OptionValue};
+use crate::shared::Transforms;
+use derive_more::Display;
+
+/// The mode (resolution and refresh rate) of a monitor
+#[derive(Debug, Clone, Copy, PartialEq, Display)]
+pub enum MonitorMode {
+    /// Uses the preferred mode of the monitor
+    #[display("preferred")]
+    Preferred,
+    /// Prefers the highest resolution
+    #[display("highres")]
+    HighRes,
+    /// Prefers the highest refresh rate
+    #[display("highrr")]
+    HighRr,
+    /// An explicit resolution, with an optional refresh rate
+    #[display("{width}x{height}{}", refresh_rate.map(|r| format!("@{r}")).unwrap_or_default())]
+    Resolution {
+        /// The width in pixels
+        width: u16,
+        /// The height in pixels
+        height: u16,
+        /// The refresh rate in hertz
+        refresh_rate: Option<f32>,
+    },
+}
+
+/// The position of a monitor in the layout
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display)]
+pub enum MonitorPosition {
+    /// Lets Hyprland place the monitor to the right of the others
+    #[display("auto")]
+    Auto,
+    /// Places the monitor to the left of the others
+    #[display("auto-left")]
+    AutoLeft,
+    /// Places the monitor to the right of the others
+    #[display("auto-right")]
+    AutoRight,
+    /// Places the monitor above the others
+    #[display("auto-up")]
+    AutoUp,
+    /// Places the monitor below the others
+    #[display("auto-down")]
+    AutoDown,
+    /// An explicit position in layout pixels
+    #[display("{_0}x{_1}")]
+    Explicit(i32, i32),
+}
+
+/// The variable refresh rate mode of a monitor
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display)]
+pub enum VrrMode {
+    /// VRR is disabled
+    #[display("0")]
+    Off,
+    /// VRR is always enabled
+    #[display("1")]
+    On,
+    /// VRR is only enabled for fullscreen windows
+    #[display("2")]
+    Fullscreen,
+}
+
+/// A monitor (`monitor`) config builder
+///
+/// ```rust
+/// use hyprland::config::monitors::*;
+/// use hyprland::shared::Transforms;
+/// let monitor = MonitorConfig::new("DP-1")
+///     .resolution(2560, 1440, Some(144.0))
+///     .position(MonitorPosition::Explicit(0, 0))
+///     .scale(1.25)
+///     .transform(Transforms::Normal90)
+///     .vrr(VrrMode::On);
+/// assert_eq!(
+///     monitor.to_string(),
+///     "monitor = DP-1,2560x1440@144,0x0,1.25,transform,1,vrr,1"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct MonitorConfig {
+    /// The monitor name (or `desc:` followed by its description)
+    pub name: String,
+    /// The monitor mode
+    pub mode: MonitorMode,
+    /// The monitor position
+    pub position: MonitorPosition,
+    /// The monitor scale, [None] lets Hyprland pick one
+    pub scale: Option<f32>,
+    /// The monitor transform
+    pub transform: Option<Transforms>,
+    /// The name of the monitor to mirror
+    pub mirror: Option<String>,
+    /// The bit depth (8 or 10)
+    pub bitdepth: Option<u8>,
+    /// The variable refresh rate mode
+    pub vrr: Option<VrrMode>,
+    /// If the monitor should be disabled
+    pub disabled: bool,
+}
+
+impl MonitorConfig {
+    /// Creates a new monitor config using the preferred mode and automatic position and scale
+    pub fn new<Str: Into<String>>(name: Str) -> Self {
+        Self {
+            name: name.into(),
+            mode: MonitorMode::Preferred,
+            position: MonitorPosition::Auto,
+            scale: None,
+            transform: None,
+            mirror: None,
+            bitdepth: None,
+            vrr: None,
+            disabled: false,
+        }
+    }
+
+    /// Sets the monitor mode
+    pub fn mode(mut self, mode: MonitorMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Sets an explicit resolution and refresh rate
+    pub fn resolution(self, width: u16, height: u16, refresh_rate: Option<f32>) -> Self {
+        self.mode(MonitorMode::Resolution {
+            width,
+            height,
+            refresh_rate,
+        })
+    }
+
+    /// Sets the monitor position
+    pub fn position(mut self, position: MonitorPosition) -> Self {
+        self.position = position;
+        self
+    }
+
+    /// Sets the monitor scale
+    pub fn scale(mut self, scale: f32) -> Self {
+        self.scale = Some(scale);
+        self
+    }
+
+    /// Sets the monitor transform
+    pub fn transform(mut self, transform: Transforms) -> Self {
+        self.transform = Some(transform);
+        self
+    }
+
+    /// Mirrors another monitor
+    pub fn mirror<Str: Into<String>>(mut self, name: Str) -> Self {
+        self.mirror = Some(name.into());
+        self
+    }
+
+    /// Sets the bit depth (8 or 10)
+    pub fn bitdepth(mut self, bitdepth: u8) -> Self {
+        self.bitdepth = Some(bitdepth);
+        self
+    }
+
+    /// Sets the variable refresh rate mode
+    pub fn vrr(mut self, vrr: VrrMode) -> Self {
+        self.vrr = Some(vrr);
+        self
+    }
+
+    /// Disables the monitor
+    pub fn disable(mut self) -> Self {
+        self.disabled = true;
+        self
+    }
+
+    /// Returns the value passed to the `monitor` keyword
+    pub fn value(&self) -> crate::Result<String> {
+        if self.name.is_empty() {
+            return Err(HyprError::InvalidConfig(
+                "a monitor config needs a name".to_string(),
+            ));
+        }
+        if matches!(
+            self.mode,
+            MonitorMode::Resolution { width, height, refresh_rate }
+                if width == 0 || height == 0 || refresh_rate.is_some_and(|r| r <= 0.0)
+        ) {
+            return Err(HyprError::InvalidConfig(format!(
+                "invalid monitor mode {}",
+                self.mode
+            )));
+        }
+        if let Some(scale) = self.scale.filter(|scale| *scale <= 0.0) {
+            return Err(HyprError::InvalidConfig(format!(
+                "invalid monitor scale {scale}"
+            )));
+        }
+        if let Some(bitdepth) = self
+            .bitdepth
+            .filter(|bitdepth| *bitdepth != 8 && *bitdepth != 10)
+        {
+            return Err(HyprError::InvalidConfig(format!(
+                "invalid monitor bitdepth {bitdepth}, expected 8 or 10"
+            )));
+        }
+        Ok(self.gen_value())
+    }
+
+    fn gen_value(&self) -> String {
+        if self.disabled {
+            return format!("{},disable", self.name);
+        }
+        let scale = match self.scale {
+            Some(scale) => scale.to_string(),
+            None => "auto".to_string(),
+        };
+        let mut value = format!("{},{},{},{scale}", self.name, self.mode, self.position);
+        if let Some(transform) = self.transform {
+            value.push_str(&format!(",transform,{}", transform as u8));
+        }
+        if let Some(mirror) = &self.mirror {
+            value.push_str(&format!(",mirror,{mirror}"));
+        }
+        if let Some(bitdepth) = self.bitdepth {
+            value.push_str(&format!(",bitdepth,{bitdepth}"));
+        }
+        if let Some(vrr) = self.vrr {
+            value.push_str(&format!(",vrr,{vrr}"));
+        }
+        value
+    }
+
+    /// Returns the config as a line for a Hyprland config file
+    pub fn to_config_line(&self) -> crate::Result<String> {
+        Ok(format!("monitor = {}", self.value()?))
+    }
+
+    /// Applies the config at runtime
+    pub fn apply(&self) -> crate::Result<()> {
+        self.instance_apply(default_instance()?)
+    }
+
+    /// Applies the config at runtime
+    pub fn instance_apply(&self, instance: &Instance) -> crate::Result<()> {
+        Keyword::instance_set(instance, "monitor", OptionValue::String(self.value()?))
+    }
+
+    /// Applies the config at runtime (async)
+    #[cfg(any(feature = "async-lite", feature = "tokio"))]
+    pub async fn apply_async(&self) -> crate::Result<()> {
+        self.instance_apply_async(default_instance()?).await
+    }
+
+    /// Applies the config at runtime (async)
+    #[cfg(any(feature = "async-lite", feature = "tokio"))]
+    pub async fn instance_apply_async(&self, instance: &Instance) -> crate::Result<()> {
+        Keyword::instance_set_async(instance, "monitor", OptionValue::String(self.value()?)).await
+    }
+}
+
+impl std::fmt::Display for MonitorConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let line = self.to_config_line().map_err(|_| std::fmt::Error)?;
+        write!(f, "{line}")
+    }
+}
+
+#[cfg(feature = "data")]
+impl From<&Monitor> for MonitorConfig {
+    fn from(monitor: &Monitor) -> Self {
+        Self {
+            name: monitor.name.clone(),
+            mode: MonitorMode::Resolution {
+                width: monitor.width,
+                height: monitor.height,
+                refresh_rate: Some(monitor.refresh_rate),
+            },
+            position: MonitorPosition::Explicit(monitor.x, monitor.y),
+            scale: Some(monitor.scale),
+            transform: Some(monitor.transform),
+            mirror: None,
+            bitdepth: None,
+            vrr: Some(if monitor.vrr {
+                VrrMode::On
+            } else {
+                VrrMode::Off
+            }),
+            disabled: monitor.disabled,
+        }
+    }
+}
+
+#[cfg(feature = "data")]
+impl From<Monitor> for MonitorConfig {
+    fn from(monitor: Monitor) -> Self {
+        Self::from(&monitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn test_monitor_config() {
+        assert_eq!(
+            MonitorConfig::new("eDP-1").value().ok(),
+            Some("eDP-1,preferred,auto,auto".to_string())
+        );
+        let monitor = MonitorConfig::new("HDMI-A-1")
+            .mode(MonitorMode::HighRr)
+            .position(MonitorPosition::AutoLeft)
+            .mirror("eDP-1")
+            .bitdepth(10);
+        assert_eq!(
+            monitor.to_config_line().unwrap(),
+            "monitor = HDMI-A-1,highrr,auto-left,auto,mirror,eDP-1,bitdepth,10"
+        );
+        assert_eq!(
+            MonitorConfig::new("DP-2")
+                .scale(2.0)
+                .disable()
+                .to_config_line()
+                .unwrap(),
+            "monitor = DP-2,disable"
+        );
+        assert!(MonitorConfig::new("DP-1").bitdepth(12).value().is_err());
+        assert!(MonitorConfig::new("DP-1").scale(0.0).value().is_err());
+        assert!(MonitorConfig::new("").value().is_err());
+        assert!(
+            MonitorConfig::new("DP-1")
+                .scale(0.0)
+                .to_config_line()
+                .is_err()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "data")]
+    #[allow(clippy::unwrap_used)]
+    fn test_from_monitor() {
+        use crate::data::WorkspaceBasic;
+
+        let workspace = WorkspaceBasic {
+            id: 1,
+            name: "1".to_string(),
+        };
+        let monitor = Monitor {
+            id: 0,
+            name: "DP-1".to_string(),
+            description: String::new(),
+            width: 2560,
+            height: 1440,
+            refresh_rate: 143.912,
+            x: -2560,
+            y: 0,
+            active_workspace: workspace.clone(),
+            special_workspace: workspace,
+            reserved: (0, 0, 0, 0),
+            scale: 1.25,
+            transform: Transforms::Flipped,
+            focused: true,
+            dpms_status: true,
+            vrr: true,
+            disabled: false,
+            physical_width: 0,
+            physical_height: 0,
+        };
+        assert_eq!(
+            MonitorConfig::from(monitor).to_config_line().unwrap(),
+            "monitor = DP-1,2560x1440@143.912,-2560x0,1.25,transform,4,vrr,1"
+        );
+    }
+}
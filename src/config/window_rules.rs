@@ -0,0 +1,422 @@
+use crate::default_instance;
+use crate::dispatch::TagAction;
+use crate::error::HyprError;
+use crate::instance::Instance;
+use crate::keyword::{Keyword, OptionValue};
+use crate::shared::WorkspaceId;
+use derive_more::Display;
+
+/// A length used by the size and move rules
+#[derive(Debug, Clone, Copy, PartialEq, Display)]
+pub enum RuleLength {
+    /// An amount of pixels
+    #[display("{_0}")]
+    Pixels(i64),
+    /// A percentage of the monitor size
+    #[display("{_0}%")]
+    Percent(f32),
+}
+
+/// The modes for the [WindowRuleAction::IdleInhibit] rule
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display)]
+pub enum IdleInhibitMode {
+    /// Never inhibit idle
+    #[display("none")]
+    None,
+    /// Always inhibit idle while the window is open
+    #[display("always")]
+    Always,
+    /// Inhibit idle while the window is focused
+    #[display("focus")]
+    Focus,
+    /// Inhibit idle while the window is fullscreen
+    #[display("fullscreen")]
+    Fullscreen,
+}
+
+/// The action a window rule applies to the windows it matches
+#[derive(Debug, Clone, Display)]
+pub enum WindowRuleAction {
+    /// Floats the window
+    #[display("float")]
+    Float,
+    /// Tiles the window
+    #[display("tile")]
+    Tile,
+    /// Makes the window fullscreen
+    #[display("fullscreen")]
+    Fullscreen,
+    /// Maximizes the window
+    #[display("maximize")]
+    Maximize,
+    /// Pseudo tiles the window
+    #[display("pseudo")]
+    Pseudo,
+    /// Pins the window to all workspaces
+    #[display("pin")]
+    Pin,
+    /// Centers a floating window on its monitor
+    #[display("center")]
+    Center,
+    /// Sets the size of a floating window
+    #[display("size {_0} {_1}")]
+    Size(RuleLength, RuleLength),
+    /// Sets the minimum size of a floating window
+    #[display("minsize {_0} {_1}")]
+    MinSize(i64, i64),
+    /// Sets the maximum size of a floating window
+    #[display("maxsize {_0} {_1}")]
+    MaxSize(i64, i64),
+    /// Moves a floating window
+    #[display("move {_0} {_1}")]
+    Move(RuleLength, RuleLength),
+    /// Opens the window on a workspace
+    #[display("workspace {_0}{}", if *_1 { " silent" } else { "" })]
+    Workspace(
+        /// The workspace
+        String,
+        /// Don't switch to the workspace
+        bool,
+    ),
+    /// Opens the window on a monitor
+    #[display("monitor {_0}")]
+    Monitor(String),
+    /// Sets the opacity of the window
+    #[display("opacity {_0}{}", _1.map(|i| format!(" {i}")).unwrap_or_default())]
+    Opacity(
+        /// The active opacity
+        f32,
+        /// The inactive opacity
+        Option<f32>,
+    ),
+    /// Disables blur for the window
+    #[display("noblur")]
+    NoBlur,
+    /// Disables shadows for the window
+    #[display("noshadow")]
+    NoShadow,
+    /// Disables borders for the window
+    #[display("noborder")]
+    NoBorder,
+    /// Disables animations for the window
+    #[display("noanim")]
+    NoAnim,
+    /// Disables dimming for the window
+    #[display("nodim")]
+    NoDim,
+    /// Disables focus for the window
+    #[display("nofocus")]
+    NoFocus,
+    /// Doesn't focus the window when it opens
+    #[display("noinitialfocus")]
+    NoInitialFocus,
+    /// Dims everything around the window
+    #[display("dimaround")]
+    DimAround,
+    /// Keeps focus on the window while it is visible
+    #[display("stayfocused")]
+    StayFocused,
+    /// Keeps the aspect ratio of the window when resizing
+    #[display("keepaspectratio")]
+    KeepAspectRatio,
+    /// Allows tearing for the window
+    #[display("immediate")]
+    Immediate,
+    /// Sets the blur xray mode for the window
+    #[display("xray {}", *_0 as u8)]
+    Xray(bool),
+    /// Sets the idle inhibit mode for the window
+    #[display("idleinhibit {_0}")]
+    IdleInhibit(IdleInhibitMode),
+    /// Applies a tag to the window
+    #[display("tag {_0}{_1}")]
+    Tag(TagAction, String),
+    /// Forces an animation style for the window
+    #[display("animation {_0}")]
+    Animation(String),
+    /// Sets the rounding of the window
+    #[display("rounding {_0}")]
+    Rounding(i64),
+    /// Sets the border size of the window
+    #[display("bordersize {_0}")]
+    BorderSize(i64),
+    /// Sets the group rules of the window
+    #[display("group {_0}")]
+    Group(String),
+    /// Ignores events from the window, e.g. `fullscreen maximize`
+    #[display("suppressevent {_0}")]
+    SuppressEvent(String),
+    /// Lets you use rules not supported by hyprland-rs yet
+    #[display("{_0}")]
+    Custom(String),
+}
+
+/// A property used to match the windows a rule applies to
+#[derive(Debug, Clone, PartialEq, Eq, Display)]
+pub enum WindowRuleMatcher {
+    /// A Regular Expression to match the window class
+    #[display("class:{_0}")]
+    Class(String),
+    /// A Regular Expression to match the window title
+    #[display("title:{_0}")]
+    Title(String),
+    /// A Regular Expression to match the initial window class
+    #[display("initialClass:{_0}")]
+    InitialClass(String),
+    /// A Regular Expression to match the initial window title
+    #[display("initialTitle:{_0}")]
+    InitialTitle(String),
+    /// Whether the window runs under XWayland
+    #[display("xwayland:{}", *_0 as u8)]
+    XWayland(bool),
+    /// Whether the window is floating
+    #[display("floating:{}", *_0 as u8)]
+    Floating(bool),
+    /// Whether the window is fullscreen
+    #[display("fullscreen:{}", *_0 as u8)]
+    Fullscreen(bool),
+    /// Whether the window is pinned
+    #[display("pinned:{}", *_0 as u8)]
+    Pinned(bool),
+    /// Whether the window is focused
+    #[display("focus:{}", *_0 as u8)]
+    Focus(bool),
+    /// The workspace the window is on, either an id or `name:<name>`
+    #[display("workspace:{_0}")]
+    Workspace(String),
+    /// A window tag
+    #[display("tag:{_0}")]
+    Tag(String),
+    /// Lets you use matchers not supported by hyprland-rs yet
+    #[display("{_0}")]
+    Custom(String),
+}
+
+/// A window rule (`windowrulev2`) builder
+///
+/// Every action generates its own `windowrulev2` line, all sharing the same matchers
+///
+/// ```rust
+/// use hyprland::config::window_rules::*;
+/// let rule = WindowRule::new(WindowRuleAction::Float)
+///     .action(WindowRuleAction::Center)
+///     .class("^(pavucontrol)$");
+/// assert_eq!(
+///     rule.to_string(),
+///     "windowrulev2 = float, class:^(pavucontrol)$\nwindowrulev2 = center, class:^(pavucontrol)$"
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct WindowRule {
+    /// The actions to apply
+    pub actions: Vec<WindowRuleAction>,
+    /// The properties a window has to match
+    pub matchers: Vec<WindowRuleMatcher>,
+}
+
+impl WindowRule {
+    /// Creates a new window rule with a single action
+    pub fn new(action: WindowRuleAction) -> Self {
+        Self {
+            actions: vec![action],
+            matchers: vec![],
+        }
+    }
+
+    /// Adds another action to the rule
+    pub fn action(mut self, action: WindowRuleAction) -> Self {
+        self.actions.push(action);
+        self
+    }
+
+    /// Adds a matcher to the rule
+    pub fn matcher(mut self, matcher: WindowRuleMatcher) -> Self {
+        self.matchers.push(matcher);
+        self
+    }
+
+    /// Matches windows by a class regex
+    pub fn class<Str: Into<String>>(self, regex: Str) -> Self {
+        self.matcher(WindowRuleMatcher::Class(regex.into()))
+    }
+
+    /// Matches windows by a title regex
+    pub fn title<Str: Into<String>>(self, regex: Str) -> Self {
+        self.matcher(WindowRuleMatcher::Title(regex.into()))
+    }
+
+    /// Matches windows by an initial class regex
+    pub fn initial_class<Str: Into<String>>(self, regex: Str) -> Self {
+        self.matcher(WindowRuleMatcher::InitialClass(regex.into()))
+    }
+
+    /// Matches windows by an initial title regex
+    pub fn initial_title<Str: Into<String>>(self, regex: Str) -> Self {
+        self.matcher(WindowRuleMatcher::InitialTitle(regex.into()))
+    }
+
+    /// Matches windows by whether they run under XWayland
+    pub fn xwayland(self, xwayland: bool) -> Self {
+        self.matcher(WindowRuleMatcher::XWayland(xwayland))
+    }
+
+    /// Matches windows by whether they are floating
+    pub fn floating(self, floating: bool) -> Self {
+        self.matcher(WindowRuleMatcher::Floating(floating))
+    }
+
+    /// Matches windows by whether they are fullscreen
+    pub fn fullscreen(self, fullscreen: bool) -> Self {
+        self.matcher(WindowRuleMatcher::Fullscreen(fullscreen))
+    }
+
+    /// Matches windows by whether they are pinned
+    pub fn pinned(self, pinned: bool) -> Self {
+        self.matcher(WindowRuleMatcher::Pinned(pinned))
+    }
+
+    /// Matches windows by the id of their workspace
+    pub fn workspace_id(self, id: WorkspaceId) -> Self {
+        self.matcher(WindowRuleMatcher::Workspace(id.to_string()))
+    }
+
+    /// Matches windows by the name of their workspace
+    pub fn workspace_name<Str: std::fmt::Display>(self, name: Str) -> Self {
+        self.matcher(WindowRuleMatcher::Workspace(format!("name:{name}")))
+    }
+
+    /// Matches windows by a tag
+    pub fn tag<Str: Into<String>>(self, tag: Str) -> Self {
+        self.matcher(WindowRuleMatcher::Tag(tag.into()))
+    }
+
+    /// Returns the values passed to the `windowrulev2` keyword, one per action
+    pub fn values(&self) -> crate::Result<Vec<String>> {
+        if self.actions.is_empty() {
+            return Err(HyprError::InvalidConfig(
+                "a window rule needs at least one action".to_string(),
+            ));
+        }
+        if self.matchers.is_empty() {
+            return Err(HyprError::InvalidConfig(
+                "a window rule needs at least one matcher".to_string(),
+            ));
+        }
+        Ok(self.actions.iter().map(|a| self.gen_value(a)).collect())
+    }
+
+    fn gen_value(&self, action: &WindowRuleAction) -> String {
+        let matchers = self
+            .matchers
+            .iter()
+            .map(|m| m.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!("{action}, {matchers}")
+    }
+
+    /// Returns the rule as lines for a Hyprland config file
+    pub fn to_config_lines(&self) -> crate::Result<Vec<String>> {
+        Ok(self
+            .values()?
+            .into_iter()
+            .map(|value| format!("windowrulev2 = {value}"))
+            .collect())
+    }
+
+    /// Applies the rule at runtime
+    pub fn apply(&self) -> crate::Result<()> {
+        self.instance_apply(default_instance()?)
+    }
+
+    /// Applies the rule at runtime
+    pub fn instance_apply(&self, instance: &Instance) -> crate::Result<()> {
+        for value in self.values()? {
+            Keyword::instance_set(instance, "windowrulev2", OptionValue::String(value))?;
+        }
+        Ok(())
+    }
+
+    /// Applies the rule at runtime (async)
+    #[cfg(any(feature = "async-lite", feature = "tokio"))]
+    pub async fn apply_async(&self) -> crate::Result<()> {
+        self.instance_apply_async(default_instance()?).await
+    }
+
+    /// Applies the rule at runtime (async)
+    #[cfg(any(feature = "async-lite", feature = "tokio"))]
+    pub async fn instance_apply_async(&self, instance: &Instance) -> crate::Result<()> {
+        for value in self.values()? {
+            Keyword::instance_set_async(instance, "windowrulev2", OptionValue::String(value))
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for WindowRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let lines = self.to_config_lines().map_err(|_| std::fmt::Error)?;
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[track_caller]
+    #[allow(clippy::unwrap_used)]
+    fn check(rule: WindowRule, expected: &[&str]) {
+        assert_eq!(rule.to_config_lines().unwrap(), expected);
+    }
+
+    #[test]
+    fn test_actions() {
+        check(
+            WindowRule::new(WindowRuleAction::Size(
+                RuleLength::Percent(50.0),
+                RuleLength::Pixels(400),
+            ))
+            .action(WindowRuleAction::Workspace("special:term".into(), true))
+            .action(WindowRuleAction::Opacity(0.9, Some(0.7)))
+            .action(WindowRuleAction::IdleInhibit(IdleInhibitMode::Fullscreen))
+            .action(WindowRuleAction::Tag(TagAction::Add, "media".into()))
+            .class("^(mpv)$"),
+            &[
+                "windowrulev2 = size 50% 400, class:^(mpv)$",
+                "windowrulev2 = workspace special:term silent, class:^(mpv)$",
+                "windowrulev2 = opacity 0.9 0.7, class:^(mpv)$",
+                "windowrulev2 = idleinhibit fullscreen, class:^(mpv)$",
+                "windowrulev2 = tag +media, class:^(mpv)$",
+            ],
+        );
+    }
+
+    #[test]
+    fn test_matchers() {
+        check(
+            WindowRule::new(WindowRuleAction::NoBlur)
+                .initial_class("firefox")
+                .title("^(Picture-in-Picture)$")
+                .xwayland(false)
+                .floating(true)
+                .fullscreen(false)
+                .workspace_name("web")
+                .tag("media"),
+            &[
+                "windowrulev2 = noblur, initialClass:firefox, title:^(Picture-in-Picture)$, xwayland:0, floating:1, fullscreen:0, workspace:name:web, tag:media",
+            ],
+        );
+    }
+
+    #[test]
+    fn test_needs_matcher() {
+        let rule = WindowRule::new(WindowRuleAction::Float);
+        assert!(matches!(rule.values(), Err(HyprError::InvalidConfig(_))));
+        assert!(matches!(
+            rule.to_config_lines(),
+            Err(HyprError::InvalidConfig(_))
+        ));
+    }
+}
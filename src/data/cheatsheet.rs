@@ -0,0 +1,304 @@
+use super::*;
+use serde::{Deserialize, Serialize};
+
+/// A single shortcut in a [CheatSheet]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct CheatSheetEntry {
+    /// The readable key combination, for example `SUPER + SHIFT + Q`
+    pub keys: String,
+    /// The description of the bind, or a rendering of its dispatcher
+    pub description: String,
+    /// If the description came from `bindd`
+    pub described: bool,
+    /// The dispatcher of the bind
+    pub dispatcher: String,
+    /// The argument of the dispatcher
+    pub arg: String,
+}
+
+/// The shortcuts of one submap in a [CheatSheet]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct CheatSheetSection {
+    /// The submap, [None] for the global one
+    pub submap: Option<String>,
+    /// The shortcuts, sorted by modifier
+    pub entries: Vec<CheatSheetEntry>,
+}
+
+impl CheatSheetSection {
+    /// The title of the section
+    pub fn title(&self) -> String {
+        match &self.submap {
+            Some(submap) => format!("Submap: {submap}"),
+            None => "Global".to_string(),
+        }
+    }
+}
+
+/// A shortcut cheat sheet made from [Binds], grouped by submap
+///
+/// ```rust
+/// use hyprland::data::{Bind, CheatSheet};
+/// let bind = Bind {
+///     locked: false,
+///     mouse: false,
+///     release: false,
+///     repeat: false,
+///     modmask: 65,
+///     submap: String::new(),
+///     key: "q".to_string(),
+///     keycode: 0,
+///     dispatcher: "killactive".to_string(),
+///     arg: String::new(),
+///     description: String::new(),
+/// };
+/// let sheet = CheatSheet::from_binds([&bind]);
+/// assert_eq!(sheet.to_plain_text(), "Global\n  SUPER + SHIFT + Q  Close the active window\n");
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct CheatSheet {
+    /// The sections, the global submap first and the others sorted by name
+    pub sections: Vec<CheatSheetSection>,
+}
+
+/// An entry with its mod order and lowercase key to sort by
+type SortableEntry = (Vec<usize>, String, CheatSheetEntry);
+
+impl CheatSheet {
+    /// Creates a cheat sheet from binds
+    pub fn from_binds<'a>(binds: impl IntoIterator<Item = &'a Bind>) -> Self {
+        let mut groups: Vec<(Option<String>, Vec<SortableEntry>)> = vec![];
+        for bind in binds {
+            let submap = match bind.submap.as_str() {
+                "" | "default" => None,
+                submap => Some(submap.to_string()),
+            };
+            let mods = Mod::from_modmask(bind.modmask);
+            let key = key_name(bind);
+            let mut keys: Vec<String> = mods.iter().map(|m| m.to_string()).collect();
+            keys.push(key.clone());
+            let described = !bind.description.is_empty();
+            let entry = CheatSheetEntry {
+                keys: keys.join(" + "),
+                description: if described {
+                    bind.description.clone()
+                } else {
+                    describe_dispatcher(&bind.dispatcher, &bind.arg)
+                },
+                described,
+                dispatcher: bind.dispatcher.clone(),
+                arg: bind.arg.clone(),
+            };
+            let order = mods
+                .iter()
+                .filter_map(|m| Mod::ALL.iter().position(|all| all == m))
+                .collect();
+            let item = (order, key.to_lowercase(), entry);
+            match groups.iter_mut().find(|(s, _)| *s == submap) {
+                Some((_, entries)) => entries.push(item),
+                None => groups.push((submap, vec![item])),
+            }
+        }
+        // None sorts before Some, so the global submap comes first
+        groups.sort_by(|(a, _), (b, _)| a.cmp(b));
+        let sections = groups
+            .into_iter()
+            .map(|(submap, mut entries)| {
+                entries.sort_by(|(a_mods, a_key, _), (b_mods, b_key, _)| {
+                    a_mods.cmp(b_mods).then_with(|| a_key.cmp(b_key))
+                });
+                CheatSheetSection {
+                    submap,
+                    entries: entries.into_iter().map(|(_, _, entry)| entry).collect(),
+                }
+            })
+            .collect();
+        Self { sections }
+    }
+
+    /// Renders the cheat sheet as Markdown tables
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        for (i, section) in self.sections.iter().enumerate() {
+            if i > 0 {
+                out.push('\n');
+            }
+            out.push_str(&format!("## {}\n\n", section.title()));
+            out.push_str("| Keys | Action |\n| --- | --- |\n");
+            for entry in &section.entries {
+                out.push_str(&format!(
+                    "| `{}` | {} |\n",
+                    entry.keys,
+                    entry.description.replace('|', "\\|")
+                ));
+            }
+        }
+        out
+    }
+
+    /// Renders the cheat sheet as JSON
+    pub fn to_json(&self) -> crate::Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Renders the cheat sheet as aligned plain text
+    pub fn to_plain_text(&self) -> String {
+        let mut out = String::new();
+        for (i, section) in self.sections.iter().enumerate() {
+            if i > 0 {
+                out.push('\n');
+            }
+            out.push_str(&section.title());
+            out.push('\n');
+            let width = section
+                .entries
+                .iter()
+                .map(|entry| entry.keys.chars().count())
+                .max()
+                .unwrap_or_default();
+            for entry in &section.entries {
+                out.push_str(&format!("  {:width$}  {}\n", entry.keys, entry.description));
+            }
+        }
+        out
+    }
+}
+
+impl From<&Binds> for CheatSheet {
+    fn from(binds: &Binds) -> Self {
+        Self::from_binds(binds)
+    }
+}
+
+impl std::fmt::Display for CheatSheet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_plain_text())
+    }
+}
+
+fn key_name(bind: &Bind) -> String {
+    if bind.key.is_empty() {
+        return format!("code:{}", bind.keycode);
+    }
+    match bind.key.as_str() {
+        "mouse:272" => "Left Click".to_string(),
+        "mouse:273" => "Right Click".to_string(),
+        "mouse:274" => "Middle Click".to_string(),
+        "mouse_up" => "Scroll Up".to_string(),
+        "mouse_down" => "Scroll Down".to_string(),
+        "mouse_left" => "Scroll Left".to_string(),
+        "mouse_right" => "Scroll Right".to_string(),
+        key => {
+            let mut chars = key.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        }
+    }
+}
+
+fn direction_name(arg: &str) -> &str {
+    match arg {
+        "l" | "left" => "left",
+        "r" | "right" => "right",
+        "u" | "up" | "t" | "top" => "up",
+        "d" | "down" | "b" | "bottom" => "down",
+        arg => arg,
+    }
+}
+
+fn describe_dispatcher(dispatcher: &str, arg: &str) -> String {
+    let arg = arg.trim();
+    let description = match dispatcher {
+        "exec" | "execr" => format!("Run {arg}"),
+        "killactive" => "Close the active window".to_string(),
+        "forcekillactive" => "Kill the active window".to_string(),
+        "workspace" => format!("Go to workspace {arg}"),
+        "movetoworkspace" => format!("Move the window to workspace {arg}"),
+        "movetoworkspacesilent" => format!("Move the window to workspace {arg} silently"),
+        "togglefloating" => "Toggle floating".to_string(),
+        "fullscreen" => "Toggle fullscreen".to_string(),
+        "pseudo" => "Toggle pseudo tiling".to_string(),
+        "pin" => "Pin the window".to_string(),
+        "movefocus" => format!("Focus {}", direction_name(arg)),
+        "movewindow" => format!("Move the window {}", direction_name(arg)),
+        "swapwindow" => format!("Swap the window {}", direction_name(arg)),
+        "resizeactive" => format!("Resize the window by {arg}"),
+        "moveactive" => format!("Move the window by {arg}"),
+        "togglespecialworkspace" if arg.is_empty() => "Toggle the special workspace".to_string(),
+        "togglespecialworkspace" => format!("Toggle the special workspace {arg}"),
+        "togglegroup" => "Toggle group".to_string(),
+        "changegroupactive" => "Switch the window in the group".to_string(),
+        "focusmonitor" => format!("Focus monitor {arg}"),
+        "submap" if arg == "reset" => "Leave the submap".to_string(),
+        "submap" => format!("Enter the {arg} submap"),
+        "exit" => "Exit Hyprland".to_string(),
+        "layoutmsg" => format!("Layout: {arg}"),
+        _ if arg.is_empty() => dispatcher.to_string(),
+        _ => format!("{dispatcher} {arg}"),
+    };
+    description.trim_end().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bind(modmask: u16, key: &str, submap: &str, dispatcher: &str, arg: &str) -> Bind {
+        Bind {
+            locked: false,
+            mouse: false,
+            release: false,
+            repeat: false,
+            modmask,
+            submap: submap.to_string(),
+            key: key.to_string(),
+            keycode: 0,
+            dispatcher: dispatcher.to_string(),
+            arg: arg.to_string(),
+            description: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_cheatsheet() {
+        let mut described = bind(64, "Return", "", "exec", "kitty");
+        described.description = "Open a terminal | kitty".to_string();
+        let binds = [
+            bind(0, "escape", "resize", "submap", "reset"),
+            bind(5, "l", "", "movefocus", "l"),
+            described,
+            bind(64, "r", "", "submap", "resize"),
+            bind(64, "mouse:272", "", "movewindow", ""),
+            bind(
+                0,
+                "XF86AudioMute",
+                "",
+                "exec",
+                "wpctl set-mute @DEFAULT_SINK@ toggle",
+            ),
+        ];
+        let sheet = CheatSheet::from_binds(&binds);
+        assert_eq!(
+            sheet.to_plain_text(),
+            "Global
+  XF86AudioMute       Run wpctl set-mute @DEFAULT_SINK@ toggle
+  SUPER + Left Click  Move the window
+  SUPER + R           Enter the resize submap
+  SUPER + Return      Open a terminal | kitty
+  CTRL + SHIFT + L    Focus left
+
+Submap: resize
+  Escape  Leave the submap
+"
+        );
+        assert_eq!(
+            sheet.to_markdown().lines().nth(7),
+            Some("| `SUPER + Return` | Open a terminal \\| kitty |")
+        );
+        #[allow(clippy::unwrap_used)]
+        let json: CheatSheet = serde_json::from_str(&sheet.to_json().unwrap()).unwrap();
+        assert_eq!(json, sheet);
+    }
+}
@@ -57,6 +57,11 @@ mod helpers;
 
 pub use crate::data::helpers::*;
 
+/// Shortcut cheat sheets made from binds
+mod cheatsheet;
+
+pub use crate::data::cheatsheet::*;
+
 pub use crate::data::regular::*;
 
 //// This module provides async function calls
@@ -51,27 +51,7 @@ impl WorkspaceBasic {
     }
 }
 
-/// This enum provides the different monitor transforms
-#[derive(Serialize_repr, Deserialize_repr, Debug, Clone, PartialEq, Eq, Copy)]
-#[repr(u8)]
-pub enum Transforms {
-    /// No transform
-    Normal = 0,
-    /// Rotated 90 degrees
-    Normal90 = 1,
-    /// Rotated 180 degrees
-    Normal180 = 2,
-    /// Rotated 270 degrees
-    Normal270 = 3,
-    /// Flipped
-    Flipped = 4,
-    /// Flipped and rotated 90 degrees
-    Flipped90 = 5,
-    /// Flipped and rotated 180 degrees
-    Flipped180 = 6,
-    /// Flipped and rotated 270 degrees
-    Flipped270 = 7,
-}
+pub use crate::shared::Transforms;
 
 /// This struct holds information for a monitor
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
@@ -557,32 +537,7 @@ pub struct CursorPosition {
 }
 impl_on!(CursorPosition);
 
-/// A keybinding returned from the binds command
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
-pub struct Bind {
-    /// Is it locked?
-    pub locked: bool,
-    /// Is it a mouse bind?
-    pub mouse: bool,
-    /// Does it execute on release?
-    pub release: bool,
-    /// Can it be held?
-    pub repeat: bool,
-    /// It's modmask
-    pub modmask: u16,
-    /// The submap its apart of
-    pub submap: String,
-    /// The key
-    pub key: String,
-    /// The keycode
-    pub keycode: i16,
-    /// The dispatcher to be executed
-    pub dispatcher: String,
-    /// The dispatcher arg
-    pub arg: String,
-    /// description from bind[d]
-    pub description: String,
-}
+pub use crate::shared::Bind;
 
 create_data_struct!(
     vector,
@@ -593,27 +548,37 @@ create_data_struct!(
 );
 
 /// Animation styles
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Display)]
 pub enum AnimationStyle {
     /// Slide animation
+    #[display("slide")]
     Slide,
     /// Vertical slide animation
+    #[display("slidevert")]
     SlideVert,
     /// Fading slide animation
+    #[display("slidefade")]
     SlideFade,
     /// Fading slide animation in a vertical direction
+    #[display("slidefadevert")]
     SlideFadeVert,
     /// Popin animation (with percentage)
+    #[display("popin {_0}%")]
     PopIn(u8),
     /// Fade animation
+    #[display("fade")]
     Fade,
     /// Once animation used for gradient animation
+    #[display("once")]
     Once,
     /// Loop animation used for gradient animation
+    #[display("loop")]
     Loop,
     /// No animation style
+    #[display("")]
     None,
     /// Unknown style
+    #[display("{_0}")]
     Unknown(String),
 }
 
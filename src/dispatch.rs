@@ -16,11 +16,14 @@
 
 use crate::default_instance;
 use crate::dispatch::fmt::*;
-use crate::error::HyprError;
+use crate::error::{HyprError, hypr_err};
 use crate::shared::*;
 use derive_more::Display;
 use std::string::ToString;
 
+/// Module providing a helper for typing text into windows
+pub mod typing;
+
 /// This enum is for identifying a window
 #[derive(Debug, Clone, Display)]
 pub enum WindowIdentifier<'a> {
@@ -752,10 +755,227 @@ pub enum FloatValue {
     Exact(f32),
 }
 
+impl<'a> DispatchType<'a> {
+    /// Parses a dispatcher and its argument, like the ones listed by `hyprctl binds`
+    ///
+    /// Dispatchers that can't be represented exactly by another variant are kept as
+    /// [DispatchType::Custom], so the result always generates the same dispatcher string
+    pub fn parse(dispatcher: &'a str, arg: &'a str) -> Self {
+        let expected = if arg.is_empty() {
+            dispatcher.to_string()
+        } else {
+            format!("{dispatcher},{arg}")
+        };
+        match Self::parse_known(dispatcher, arg) {
+            Some(parsed)
+                if gen_dispatch_str(parsed.clone(), false)
+                    .is_ok_and(|content| content.data == expected) =>
+            {
+                parsed
+            }
+            _ => DispatchType::Custom(dispatcher, arg),
+        }
+    }
+
+    fn parse_known(dispatcher: &'a str, arg: &'a str) -> Option<Self> {
+        use DispatchType::*;
+        use parse::*;
+        Some(match dispatcher {
+            "exec" => Exec(arg),
+            "execr" => ExecRaw(arg),
+            "global" => Global(arg),
+            "killactive" => KillActiveWindow,
+            "forcekillactive" => ForceKillActiveWindow,
+            "workspace" => Workspace(workspace(arg)?),
+            "movetoworkspace" => MoveToWorkspace(workspace(arg)?, None),
+            "movetoworkspacesilent" => MoveToWorkspaceSilent(workspace(arg)?, None),
+            "togglefloating" => ToggleFloating(None),
+            "setfloating" => SetFloating(None),
+            "settiled" => SetTiled(None),
+            "fullscreen" => ToggleFullscreen(match arg {
+                "0" => FullscreenType::Real,
+                "1" => FullscreenType::Maximize,
+                _ => FullscreenType::NoParam,
+            }),
+            "fakefullscreen" => ToggleFakeFullscreen,
+            "pseudo" => TogglePseudo,
+            "pin" => TogglePin,
+            "movefocus" => MoveFocus(direction(arg)?),
+            "movewindow" => MoveWindow(match arg.strip_prefix("mon:") {
+                Some(mon) => WindowMove::Monitor(monitor(mon)),
+                None => WindowMove::Direction(direction(arg)?),
+            }),
+            "centerwindow" => CenterWindow,
+            "cyclenext" => CycleWindow(cycle_direction(arg)?),
+            "swapnext" => SwapNext(cycle_direction(arg)?),
+            "swapwindow" => SwapWindow(direction(arg)?),
+            "focusmonitor" => FocusMonitor(monitor(arg)),
+            "toggleopaque" => ToggleOpaque,
+            "exit" => Exit,
+            "forcerendererreload" => ForceRendererReload,
+            "movecurrentworkspacetomonitor" => MoveCurrentWorkspaceToMonitor(monitor(arg)),
+            "bringactivetotop" => BringActiveToTop,
+            "togglespecialworkspace" if arg.is_empty() => ToggleSpecialWorkspace(None),
+            "focusurgentorlast" => FocusUrgentOrLast,
+            "focuscurrentorlast" => FocusCurrentOrLast,
+            "toggleswallow" => ToggleSwallow,
+            "submap" => Submap(match arg {
+                "reset" => SubmapParam::Reset,
+                name => SubmapParam::Name(name),
+            }),
+            "togglegroup" => ToggleGroup,
+            "changegroupactive" => ChangeGroupActive(switch_direction(arg)?),
+            "lockgroups" => LockGroups(lock_type(arg)?),
+            "lockactivegroups" => LockActiveGroup(lock_type(arg)?),
+            "moveintogroup" => MoveIntoGroup(direction(arg)?),
+            "movewindoworgroup" => MoveWindowOrGroup(direction(arg)?),
+            "moveoutofgroup" => MoveOutOfGroup,
+            "movegroupwindow" => MoveGroupWindow(switch_direction(arg)?),
+            "denywindowfromgroup" => DenyWindowFromGroup(binary_state(arg)?),
+            "setignoregrouplock" => SetIgnoreGroupLock(binary_state(arg)?),
+            "layoutmsg" => {
+                let (msg, param) = arg.split_once(' ').unwrap_or((arg, ""));
+                match msg {
+                    "togglesplit" => ToggleSplit,
+                    "swapsplit" => SwapSplit,
+                    "preselect" => PreSelect(direction(param)?),
+                    "addmaster" => AddMaster,
+                    "removemaster" => RemoveMaster,
+                    "orientationleft" => OrientationLeft,
+                    "orientationright" => OrientationRight,
+                    "orientationtop" => OrientationTop,
+                    "orientationbottom" => OrientationBottom,
+                    "orientationcenter" => OrientationCenter,
+                    "orientationnext" => OrientationNext,
+                    "orientationprev" => OrientationPrev,
+                    "rollnext" => RollNext,
+                    "rollprev" => RollPrev,
+                    "cyclenext" => CycleNextMaster(master_loop(param)?),
+                    "cycleprev" => CyclePrevMaster(master_loop(param)?),
+                    "swapnext" => SwapNextMaster(master_loop(param)?),
+                    "swapprev" => SwapPrevMaster(master_loop(param)?),
+                    _ => return None,
+                }
+            }
+            _ => return None,
+        })
+    }
+}
+
+mod parse {
+    use super::*;
+
+    pub(super) fn direction(arg: &str) -> Option<Direction> {
+        Some(match arg {
+            "u" => Direction::Up,
+            "d" => Direction::Down,
+            "r" => Direction::Right,
+            "l" => Direction::Left,
+            _ => return None,
+        })
+    }
+
+    pub(super) fn cycle_direction(arg: &str) -> Option<CycleDirection> {
+        Some(match arg {
+            "" => CycleDirection::Next,
+            "prev" => CycleDirection::Previous,
+            _ => return None,
+        })
+    }
+
+    pub(super) fn switch_direction(arg: &str) -> Option<WindowSwitchDirection> {
+        Some(match arg {
+            "b" => WindowSwitchDirection::Back,
+            "f" => WindowSwitchDirection::Forward,
+            index => WindowSwitchDirection::Index(index.parse().ok()?),
+        })
+    }
+
+    pub(super) fn lock_type(arg: &str) -> Option<LockType> {
+        Some(match arg {
+            "lock" => LockType::Lock,
+            "unlock" => LockType::Unlock,
+            "toggle" => LockType::ToggleLock,
+            _ => return None,
+        })
+    }
+
+    pub(super) fn binary_state(arg: &str) -> Option<BinaryState> {
+        Some(match arg {
+            "on" => BinaryState::On,
+            "off" => BinaryState::Off,
+            "toggle" => BinaryState::Toggle,
+            _ => return None,
+        })
+    }
+
+    pub(super) fn master_loop(arg: &str) -> Option<MasterLoopParam> {
+        Some(match arg {
+            "loop" => MasterLoopParam::Loop,
+            "noloop" => MasterLoopParam::NoLoop,
+            _ => return None,
+        })
+    }
+
+    fn relative(arg: &str) -> Option<i32> {
+        if arg.starts_with(['+', '-']) {
+            arg.parse().ok()
+        } else {
+            None
+        }
+    }
+
+    pub(super) fn monitor(arg: &str) -> MonitorIdentifier<'_> {
+        if let Some(dir) = direction(arg) {
+            MonitorIdentifier::Direction(dir)
+        } else if arg == "current" {
+            MonitorIdentifier::Current
+        } else if let Some(int) = relative(arg) {
+            MonitorIdentifier::Relative(int)
+        } else if let Ok(id) = arg.parse() {
+            MonitorIdentifier::Id(id)
+        } else {
+            MonitorIdentifier::Name(arg)
+        }
+    }
+
+    pub(super) fn workspace(arg: &str) -> Option<WorkspaceIdentifierWithSpecial<'_>> {
+        use WorkspaceIdentifierWithSpecial::*;
+        Some(match arg {
+            "previous" => Previous,
+            "previous_per_monitor" => PreviousPerMonitor,
+            "special" => Special(None),
+            _ => {
+                if let Some(name) = arg.strip_prefix("special:") {
+                    Special(Some(name))
+                } else if let Some(name) = arg.strip_prefix("name:") {
+                    Name(name)
+                } else if let Some(flags) = arg.strip_prefix("empty") {
+                    Empty(FirstEmpty {
+                        on_monitor: flags.contains('m'),
+                        next: flags.contains('n'),
+                    })
+                } else if let Some(int) = relative(arg) {
+                    Relative(int)
+                } else if let Some(int) = arg.strip_prefix('m').and_then(relative) {
+                    RelativeMonitor(int)
+                } else if let Some(int) = arg.strip_prefix('r').and_then(relative) {
+                    RelativeMonitorIncludingEmpty(int)
+                } else if let Some(int) = arg.strip_prefix('e').and_then(relative) {
+                    RelativeOpen(int)
+                } else {
+                    Id(arg.parse().ok()?)
+                }
+            }
+        })
+    }
+}
+
 pub(crate) fn gen_dispatch_str(cmd: DispatchType, dispatch: bool) -> crate::Result<CommandContent> {
     use DispatchType::*;
     let sep = if dispatch { " " } else { "," };
     let string_to_pass = match &cmd {
+        Custom(name, "") => name.to_string(),
         Custom(name, args) => format!("{name}{sep}{args}"),
         Exec(sh) => format!("exec{sep}{sh}"),
         ExecRaw(sh) => format!("execr{sep}{sh}"),
@@ -976,6 +1196,77 @@ impl Dispatch {
             Err(error) => Err(error),
         }
     }
+
+    /// This function calls several dispatchers in a single batched request (blocking)
+    ///
+    /// ```rust,no_run
+    /// # use hyprland::Result;
+    /// # fn main() -> Result<()> {
+    /// use hyprland::dispatch::{DispatchType,Dispatch};
+    /// Dispatch::call_batch(vec![DispatchType::Exec("kitty"), DispatchType::TogglePseudo])
+    /// # }
+    /// ```
+    pub fn call_batch(dispatch_types: Vec<DispatchType>) -> crate::Result<()> {
+        Self::instance_call_batch(default_instance()?, dispatch_types)
+    }
+
+    /// This function calls several dispatchers in a single batched request (blocking)
+    pub fn instance_call_batch(
+        instance: &crate::instance::Instance,
+        dispatch_types: Vec<DispatchType>,
+    ) -> crate::Result<()> {
+        if dispatch_types.is_empty() {
+            return Ok(());
+        }
+        let output = instance.write_to_socket(gen_batch_str(dispatch_types)?)?;
+        check_batch_output(&output)
+    }
+
+    /// This function calls several dispatchers in a single batched request (async)
+    #[cfg(any(feature = "async-lite", feature = "tokio"))]
+    pub async fn call_batch_async(dispatch_types: Vec<DispatchType<'_>>) -> crate::Result<()> {
+        Self::instance_call_batch_async(default_instance()?, dispatch_types).await
+    }
+
+    /// This function calls several dispatchers in a single batched request (async)
+    #[cfg(any(feature = "async-lite", feature = "tokio"))]
+    pub async fn instance_call_batch_async(
+        instance: &crate::instance::Instance,
+        dispatch_types: Vec<DispatchType<'_>>,
+    ) -> crate::Result<()> {
+        if dispatch_types.is_empty() {
+            return Ok(());
+        }
+        let output = instance
+            .write_to_socket_async(gen_batch_str(dispatch_types)?)
+            .await?;
+        check_batch_output(&output)
+    }
+}
+
+pub(crate) fn gen_batch_str(dispatch_types: Vec<DispatchType>) -> crate::Result<CommandContent> {
+    let mut commands = Vec::with_capacity(dispatch_types.len());
+    for dispatch_type in dispatch_types {
+        let command = gen_dispatch_str(dispatch_type, true)?.data;
+        // Hyprland splits batches on `;`, so it can't appear in a command
+        if command.contains(';') {
+            hypr_err!("Can't batch a dispatcher containing `;`: {command}");
+        }
+        commands.push(command);
+    }
+    Ok(command!(Empty, "[[BATCH]]{}", commands.join(";")))
+}
+
+fn check_batch_output(output: &str) -> crate::Result<()> {
+    // Hyprland separates the replies of a batch with empty lines
+    match output
+        .split("\n\n\n")
+        .map(str::trim)
+        .find(|reply| !reply.is_empty() && *reply != "ok")
+    {
+        Some(msg) => Err(HyprError::NotOkDispatch(msg.to_string())),
+        None => Ok(()),
+    }
 }
 
 /// Macro abstraction over [Dispatch::call]
@@ -1006,3 +1297,56 @@ macro_rules! dispatch {
         $crate::dispatch::Dispatch::instance_call($instance, $crate::dispatch::DispatchType::$dis($($arg), *))
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[track_caller]
+    fn check(dispatcher: &str, arg: &str, custom: bool) {
+        let parsed = DispatchType::parse(dispatcher, arg);
+        assert_eq!(
+            matches!(parsed, DispatchType::Custom(..)),
+            custom,
+            "{parsed:?}"
+        );
+        let expected = if arg.is_empty() {
+            dispatcher.to_string()
+        } else {
+            format!("{dispatcher},{arg}")
+        };
+        #[allow(clippy::unwrap_used)]
+        let generated = gen_dispatch_str(parsed, false).unwrap().data;
+        if custom {
+            assert_eq!(generated, format!("{dispatcher},{arg}"));
+        } else {
+            assert_eq!(generated, expected);
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        check("exec", "kitty --single-instance", false);
+        check("killactive", "", false);
+        check("workspace", "3", false);
+        check("workspace", "e+1", false);
+        check("workspace", "m-2", false);
+        check("workspace", "name:web", false);
+        check("workspace", "emptymn", false);
+        check("movetoworkspacesilent", "special:scratch", false);
+        check("movefocus", "l", false);
+        check("movewindow", "mon:DP-1", false);
+        check("focusmonitor", "+1", false);
+        check("submap", "reset", false);
+        check("changegroupactive", "f", false);
+        check("layoutmsg", "togglesplit", false);
+        check("layoutmsg", "cyclenext loop", false);
+        check("fullscreen", "1", false);
+        // not representable exactly, kept as is
+        check("killactive", "extra", true);
+        check("movefocus", "left", true);
+        check("togglespecialworkspace", "term", true);
+        check("resizeactive", "10 0", true);
+        check("someplugin:dispatcher", "arg", true);
+    }
+}
@@ -0,0 +1,314 @@
+use super::{Dispatch, DispatchType, WindowIdentifier};
+use crate::default_instance;
+use crate::instance::Instance;
+use crate::shared::Mod;
+use std::time::Duration;
+
+/// The default amount of shortcuts sent in a single request
+pub const DEFAULT_BATCH_SIZE: usize = 32;
+
+/// A key combination sent to a window with `sendshortcut`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Chord {
+    /// The modifiers held while pressing the key
+    pub mods: Vec<Mod>,
+    /// The XKB name of the key, e.g. `a` or `Return`
+    pub key: String,
+}
+
+impl Chord {
+    /// Creates a new chord
+    pub fn new<Str: Into<String>>(mods: &[Mod], key: Str) -> Self {
+        Self {
+            mods: mods.to_vec(),
+            key: key.into(),
+        }
+    }
+
+    /// Creates the chord that types a character on a US layout
+    ///
+    /// Characters without a key of their own are sent as their unicode keysym (`U20AC` for `€`),
+    /// which only works if the keymap of the window has them
+    pub fn from_char(c: char) -> Self {
+        let (shift, key) = match c {
+            'a'..='z' | '0'..='9' => (false, c.to_string()),
+            'A'..='Z' => (true, c.to_ascii_lowercase().to_string()),
+            c => match char_key(c) {
+                Some((shift, key)) => (shift, key.to_string()),
+                None => (false, format!("U{:04X}", c as u32)),
+            },
+        };
+        let mods: &[Mod] = if shift { &[Mod::SHIFT] } else { &[] };
+        Self::new(mods, key)
+    }
+
+    fn dispatch_type<'a>(&'a self, target: &WindowIdentifier<'a>) -> DispatchType<'a> {
+        DispatchType::SendShortcut(&self.mods, &self.key, Some(target.clone()))
+    }
+}
+
+/// Returns if shift is needed and the key name of a non-alphanumeric character
+fn char_key(c: char) -> Option<(bool, &'static str)> {
+    Some(match c {
+        ' ' => (false, "space"),
+        '\n' => (false, "Return"),
+        '\t' => (false, "Tab"),
+        '-' => (false, "minus"),
+        '=' => (false, "equal"),
+        '[' => (false, "bracketleft"),
+        ']' => (false, "bracketright"),
+        '\\' => (false, "backslash"),
+        ';' => (false, "semicolon"),
+        '\'' => (false, "apostrophe"),
+        ',' => (false, "comma"),
+        '.' => (false, "period"),
+        '/' => (false, "slash"),
+        '`' => (false, "grave"),
+        '!' => (true, "1"),
+        '@' => (true, "2"),
+        '#' => (true, "3"),
+        '$' => (true, "4"),
+        '%' => (true, "5"),
+        '^' => (true, "6"),
+        '&' => (true, "7"),
+        '*' => (true, "8"),
+        '(' => (true, "9"),
+        ')' => (true, "0"),
+        '_' => (true, "minus"),
+        '+' => (true, "equal"),
+        '{' => (true, "bracketleft"),
+        '}' => (true, "bracketright"),
+        '|' => (true, "backslash"),
+        ':' => (true, "semicolon"),
+        '"' => (true, "apostrophe"),
+        '<' => (true, "comma"),
+        '>' => (true, "period"),
+        '?' => (true, "slash"),
+        '~' => (true, "grave"),
+        _ => return None,
+    })
+}
+
+/// A step of a [Typing] sequence
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TypingStep {
+    /// Sends a chord
+    Chord(Chord),
+    /// Waits before sending the next chord
+    Delay(Duration),
+}
+
+/// A request made while sending a [Typing] sequence
+#[derive(Debug, Clone)]
+pub enum TypingRequest<'a> {
+    /// `sendshortcut` dispatchers sent in a single batched request
+    Batch(Vec<DispatchType<'a>>),
+    /// A wait between two requests
+    Delay(Duration),
+}
+
+/// Types text and chords into a window, through batched `sendshortcut` dispatchers
+///
+/// ```rust
+/// use hyprland::dispatch::WindowIdentifier;
+/// use hyprland::dispatch::typing::*;
+/// use std::time::Duration;
+/// let typing = Typing::new(WindowIdentifier::ClassRegularExpression("kitty"))
+///     .text("Hi!")
+///     .delay(Duration::from_millis(100))
+///     .chord(Chord::new(&[], "Return"));
+/// assert_eq!(typing.steps.len(), 5);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Typing<'a> {
+    /// The window to type into
+    pub target: WindowIdentifier<'a>,
+    /// The chords and delays to send
+    pub steps: Vec<TypingStep>,
+    /// A delay added after every chord
+    pub key_delay: Option<Duration>,
+    /// The maximum amount of chords sent in a single request
+    pub batch_size: usize,
+}
+
+impl<'a> Typing<'a> {
+    /// Creates an empty sequence typing into a window
+    pub fn new(target: WindowIdentifier<'a>) -> Self {
+        Self {
+            target,
+            steps: vec![],
+            key_delay: None,
+            batch_size: DEFAULT_BATCH_SIZE,
+        }
+    }
+
+    /// Types text, pressing shift for shifted characters
+    pub fn text(mut self, text: &str) -> Self {
+        self.steps
+            .extend(text.chars().map(|c| TypingStep::Chord(Chord::from_char(c))));
+        self
+    }
+
+    /// Sends a chord
+    pub fn chord(mut self, chord: Chord) -> Self {
+        self.steps.push(TypingStep::Chord(chord));
+        self
+    }
+
+    /// Sends several chords
+    pub fn chords(mut self, chords: impl IntoIterator<Item = Chord>) -> Self {
+        self.steps.extend(chords.into_iter().map(TypingStep::Chord));
+        self
+    }
+
+    /// Waits before sending the next chord
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.steps.push(TypingStep::Delay(delay));
+        self
+    }
+
+    /// Waits after every chord, which sends each chord in its own request
+    pub fn key_delay(mut self, delay: Duration) -> Self {
+        self.key_delay = Some(delay);
+        self
+    }
+
+    /// Sets the maximum amount of chords sent in a single request
+    pub fn batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size;
+        self
+    }
+
+    /// Returns the requests made to send the sequence
+    pub fn requests(&self) -> Vec<TypingRequest<'_>> {
+        let batch_size = match self.key_delay {
+            Some(_) => 1,
+            None => self.batch_size.max(1),
+        };
+        let mut requests = vec![];
+        let mut batch = vec![];
+        for step in &self.steps {
+            match step {
+                TypingStep::Chord(chord) => {
+                    batch.push(chord.dispatch_type(&self.target));
+                    if batch.len() == batch_size {
+                        requests.push(TypingRequest::Batch(std::mem::take(&mut batch)));
+                        if let Some(delay) = self.key_delay {
+                            requests.push(TypingRequest::Delay(delay));
+                        }
+                    }
+                }
+                TypingStep::Delay(delay) => {
+                    if !batch.is_empty() {
+                        requests.push(TypingRequest::Batch(std::mem::take(&mut batch)));
+                    }
+                    requests.push(TypingRequest::Delay(*delay));
+                }
+            }
+        }
+        if !batch.is_empty() {
+            requests.push(TypingRequest::Batch(batch));
+        }
+        // A trailing delay would only make the caller wait
+        if let Some(TypingRequest::Delay(_)) = requests.last() {
+            requests.pop();
+        }
+        requests
+    }
+
+    /// Sends the sequence (blocking)
+    pub fn send(&self) -> crate::Result<()> {
+        self.instance_send(default_instance()?)
+    }
+
+    /// Sends the sequence (blocking)
+    pub fn instance_send(&self, instance: &Instance) -> crate::Result<()> {
+        for request in self.requests() {
+            match request {
+                TypingRequest::Batch(batch) => Dispatch::instance_call_batch(instance, batch)?,
+                TypingRequest::Delay(delay) => std::thread::sleep(delay),
+            }
+        }
+        Ok(())
+    }
+
+    /// Sends the sequence (async)
+    #[cfg(any(feature = "async-lite", feature = "tokio"))]
+    pub async fn send_async(&self) -> crate::Result<()> {
+        self.instance_send_async(default_instance()?).await
+    }
+
+    /// Sends the sequence (async)
+    #[cfg(any(feature = "async-lite", feature = "tokio"))]
+    pub async fn instance_send_async(&self, instance: &Instance) -> crate::Result<()> {
+        for request in self.requests() {
+            match request {
+                TypingRequest::Batch(batch) => {
+                    Dispatch::instance_call_batch_async(instance, batch).await?
+                }
+                TypingRequest::Delay(delay) => crate::async_import::sleep(delay).await,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dispatch::gen_batch_str;
+
+    fn requests(typing: &Typing) -> Vec<String> {
+        typing
+            .requests()
+            .into_iter()
+            .map(|request| match request {
+                TypingRequest::Batch(batch) => match gen_batch_str(batch) {
+                    Ok(content) => content.to_string(),
+                    Err(e) => panic!("Error occured: {e}"),
+                },
+                TypingRequest::Delay(delay) => format!("{delay:?}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_typing() {
+        assert_eq!(Chord::from_char('Q'), Chord::new(&[Mod::SHIFT], "q"));
+        assert_eq!(Chord::from_char('?'), Chord::new(&[Mod::SHIFT], "slash"));
+        assert_eq!(Chord::from_char('€'), Chord::new(&[], "U20AC"));
+
+        let typing = Typing::new(WindowIdentifier::ClassRegularExpression("kitty"))
+            .text("Hi!")
+            .delay(Duration::from_millis(100))
+            .chord(Chord::new(&[Mod::CTRL], "Return"))
+            .delay(Duration::from_millis(50));
+        assert_eq!(
+            requests(&typing),
+            [
+                "/[[BATCH]]dispatch sendshortcut SHIFT,h,class:kitty;\
+                 dispatch sendshortcut ,i,class:kitty;\
+                 dispatch sendshortcut SHIFT,1,class:kitty",
+                "100ms",
+                "/[[BATCH]]dispatch sendshortcut CTRL,Return,class:kitty",
+            ]
+        );
+
+        let typing = Typing::new(WindowIdentifier::ClassRegularExpression("kitty"))
+            .text("abc")
+            .batch_size(2);
+        assert_eq!(requests(&typing).len(), 2);
+        let typing = typing.key_delay(Duration::from_millis(10));
+        assert_eq!(
+            requests(&typing)[..2],
+            ["/[[BATCH]]dispatch sendshortcut ,a,class:kitty", "10ms"]
+        );
+        assert_eq!(requests(&typing).len(), 5);
+
+        let typing = Typing::new(WindowIdentifier::Title("a;b")).text("a");
+        match &typing.requests()[0] {
+            TypingRequest::Batch(batch) => assert!(gen_batch_str(batch.clone()).is_err()),
+            request => panic!("unexpected request {request:?}"),
+        }
+    }
+}
@@ -21,6 +21,15 @@ pub enum HyprError {
     InvalidOptionKey(String),
     /// Unparsable Option
     InvalidOptionValue,
+    /// A config builder was given values Hyprland would reject
+    #[display("Invalid config: {_0}")]
+    InvalidConfig(String),
+    /// A bind would conflict with binds that are already registered
+    #[display("The bind conflicts with {} existing bind(s)", _0.len())]
+    BindConflict(Vec<crate::shared::Bind>),
+    /// An event subscriber fell behind and missed this many events
+    #[display("The subscriber lagged behind and missed {_0} event(s)")]
+    Lagged(u64),
     /// Internal Hyprland error
     Internal(String),
     /// Error that occurs for other reasons. Avoid using this.
@@ -44,6 +53,9 @@ impl HyprError {
             Self::Hyprpaper(_) => Err(self),
             Self::InvalidOptionKey(key) => Ok(Self::InvalidOptionKey(key.clone())),
             Self::InvalidOptionValue => Ok(Self::InvalidOptionValue),
+            Self::InvalidConfig(s) => Ok(Self::InvalidConfig(s.clone())),
+            Self::BindConflict(binds) => Ok(Self::BindConflict(binds.clone())),
+            Self::Lagged(n) => Ok(Self::Lagged(*n)),
             Self::Internal(s) => Ok(Self::Internal(s.clone())),
             Self::Other(s) => Ok(Self::Other(s.clone())),
         }
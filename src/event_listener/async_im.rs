@@ -1,6 +1,8 @@
 use super::*;
 use crate::default_instance;
 use crate::instance::Instance;
+use std::sync::{Arc, Mutex, MutexGuard, Weak};
+use std::time::Instant;
 
 /// This struct is used for adding event handlers and executing them on events
 /// # The Event Listener
@@ -11,17 +13,80 @@ use crate::instance::Instance;
 ///
 /// ```rust, no_run
 /// # use hyprland::event_listener;
-/// # use hyprland_macros::async_closure;
 /// #[tokio::main(flavor = "current_thread")]
 /// async fn function() -> hyprland::Result<()> {
 ///     let mut listener = event_listener::AsyncEventListener::new();
-///     listener.add_workspace_changed_handler(async_closure! { |id| println!("workspace changed to {id:?}") });
+///     listener.add_workspace_changed_handler(async |id| println!("workspace changed to {id:?}"));
 ///     listener.start_listener_async().await?;
 ///     Ok(())
 /// }
 /// ```
+///
+/// ## Handlers
+///
+/// Adding a handler returns a [HandlerId] that can remove it again. Clones of the listener share
+/// its handlers, so a clone moved into a handler can add or remove handlers while the listener
+/// runs. Changes made while an event is handled apply from the next event
+///
+/// Handlers can return a [crate::Result]. Errors are handled by the [ErrorPolicy] set with
+/// [AsyncEventListener::set_error_policy], and [Middleware] added with [AsyncEventListener::add_middleware] runs
+/// around the handlers of every event
+///
+/// Handlers are async closures, or closures returning a future. They are taken as `Fn` returning
+/// a `'static` future instead of `AsyncFn`, because stable Rust can't require the future of an
+/// `AsyncFn` to be [Send]. So the future can't borrow what the handler captured, clone it into
+/// the future instead, and share state between events through an [Arc]
+///
+/// ```rust, no_run
+/// # use hyprland::event_listener::AsyncEventListener;
+/// # use std::sync::{Arc, atomic::{AtomicUsize, Ordering}};
+/// let listener = AsyncEventListener::new();
+/// let opened = Arc::new(AtomicUsize::new(0));
+/// listener.add_window_opened_handler(move |data| {
+///     let opened = opened.clone();
+///     async move {
+///         let count = opened.fetch_add(1, Ordering::Relaxed) + 1;
+///         println!("{count} windows opened, the last one is {}", data.window_title);
+///     }
+/// });
+/// ```
+#[derive(Clone)]
 pub struct AsyncEventListener {
-    pub(crate) events: AsyncEvents,
+    pub(crate) events: Arc<Mutex<AsyncEvents>>,
+    shutdown: ShutdownHandle,
+}
+
+/// Removes a handler from an [AsyncEventListener] when dropped, see [AsyncEventListener::guard]
+#[must_use = "the handler is removed when the guard is dropped"]
+pub struct AsyncHandlerGuard {
+    id: HandlerId,
+    events: Weak<Mutex<AsyncEvents>>,
+}
+
+impl AsyncHandlerGuard {
+    /// Returns the id of the handler
+    pub fn id(&self) -> HandlerId {
+        self.id
+    }
+
+    /// Keeps the handler, dropping the guard without removing it
+    pub fn disarm(mut self) -> HandlerId {
+        self.events = Weak::new();
+        self.id
+    }
+}
+
+impl Drop for AsyncHandlerGuard {
+    fn drop(&mut self) {
+        if let Some(events) = self.events.upgrade() {
+            lock(&events).remove(self.id);
+        }
+    }
+}
+
+fn lock(events: &Mutex<AsyncEvents>) -> MutexGuard<'_, AsyncEvents> {
+    // handlers run without the lock held, so a poisoned lock still holds valid handlers
+    events.lock().unwrap_or_else(|e| e.into_inner())
 }
 
 impl Default for AsyncEventListener {
@@ -39,7 +104,42 @@ impl AsyncEventListener {
     /// ```
     pub fn new() -> Self {
         Self {
-            events: create_events_async(),
+            events: Arc::new(Mutex::new(create_events_async())),
+            shutdown: ShutdownHandle::new(),
+        }
+    }
+
+    /// Returns a handle that stops the listener from another thread or task
+    pub fn shutdown_handle(&self) -> ShutdownHandle {
+        self.shutdown.clone()
+    }
+
+    pub(crate) fn lock_events(&self) -> MutexGuard<'_, AsyncEvents> {
+        lock(&self.events)
+    }
+
+    /// Removes a handler, returning if it was found
+    pub fn remove_handler(&self, id: HandlerId) -> bool {
+        self.lock_events().remove(id)
+    }
+
+    /// Adds middleware running around the handlers of every event, in the order it was added
+    pub fn add_middleware(&self, middleware: impl Middleware + Send + Sync + 'static) {
+        self.lock_events().middleware.push(Arc::new(middleware));
+    }
+
+    /// Sets what happens when a handler returns an error
+    ///
+    /// By default ([ErrorPolicy::Log]) the errors are printed to stderr
+    pub fn set_error_policy(&self, policy: ErrorPolicy) {
+        self.lock_events().error_policy = policy;
+    }
+
+    /// Returns a guard that removes a handler when dropped
+    pub fn guard(&self, id: HandlerId) -> AsyncHandlerGuard {
+        AsyncHandlerGuard {
+            id,
+            events: Arc::downgrade(&self.events),
         }
     }
 
@@ -48,11 +148,10 @@ impl AsyncEventListener {
     /// This should be ran after all of your handlers are defined
     /// ```rust, no_run
     /// # use hyprland::event_listener;
-    /// # use hyprland_macros::async_closure;
     /// #[tokio::main(flavor = "current_thread")]
     /// async fn function() -> hyprland::Result<()> {
     ///     let mut listener = event_listener::AsyncEventListener::new();
-    ///     listener.add_workspace_changed_handler(async_closure! { |id| println!("workspace changed to {id:?}") });
+    ///     listener.add_workspace_changed_handler(async |id| println!("workspace changed to {id:?}"));
     ///     listener.start_listener_async().await?;
     ///     Ok(())
     /// }
@@ -67,11 +166,10 @@ impl AsyncEventListener {
     /// This should be ran after all of your handlers are defined
     /// ```rust, no_run
     /// # use hyprland::{default_instance, event_listener};
-    /// # use hyprland_macros::async_closure;
     /// #[tokio::main(flavor = "current_thread")]
     /// async fn function() -> hyprland::Result<()> {
     ///     let mut listener = event_listener::AsyncEventListener::new();
-    ///     listener.add_workspace_changed_handler(async_closure! { |id| println!("workspace changed to {id:?}") });
+    ///     listener.add_workspace_changed_handler(async |id| println!("workspace changed to {id:?}"));
     ///     let instance = default_instance()?;
     ///     listener.instance_start_listener_async(instance).await?;
     ///     Ok(())
@@ -81,25 +179,132 @@ impl AsyncEventListener {
         &mut self,
         instance: &Instance,
     ) -> crate::Result<()> {
-        use crate::async_import::*;
+        self.instance_run_until_async(instance, None).await?;
+        Ok(())
+    }
 
-        let mut stream = instance.get_event_stream_async().await?;
-        let mut active_windows = vec![];
-        loop {
-            let mut buffer = [0; 4096];
-            let bytes_read = stream.read(&mut buffer).await?;
-            if bytes_read == 0 {
-                // If no bytes were read, we can assume the stream is closed
-                break;
+    /// Runs the event listener until the deadline passes, it is shut down or the socket closes,
+    /// returning the fully read events it didn't handle, see [ShutdownHandle] (async)
+    ///
+    /// Pass [None] to run without a deadline
+    /// ```rust, no_run
+    /// # use hyprland::event_listener;
+    /// use std::time::{Duration, Instant};
+    /// #[tokio::main(flavor = "current_thread")]
+    /// async fn function() -> hyprland::Result<()> {
+    ///     let mut listener = event_listener::AsyncEventListener::new();
+    ///     listener.add_workspace_changed_handler(async |id| println!("workspace changed to {id:?}"));
+    ///     let handle = listener.shutdown_handle();
+    ///     tokio::spawn(async move {
+    ///         tokio::time::sleep(Duration::from_secs(10)).await;
+    ///         handle.shutdown();
+    ///     });
+    ///     listener.run_until_async(Instant::now() + Duration::from_secs(60)).await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn run_until_async(
+        &mut self,
+        deadline: impl Into<Option<Instant>>,
+    ) -> crate::Result<Vec<Event>> {
+        self.instance_run_until_async(default_instance()?, deadline)
+            .await
+    }
+
+    /// Runs the event listener until the deadline passes, it is shut down or the socket closes,
+    /// returning the fully read events it didn't handle, see [ShutdownHandle] (async)
+    ///
+    /// Pass [None] to run without a deadline
+    pub async fn instance_run_until_async(
+        &mut self,
+        instance: &Instance,
+        deadline: impl Into<Option<Instant>>,
+    ) -> crate::Result<Vec<Event>> {
+        let shutdown = self.shutdown.clone();
+        run_async_executor(self, instance, &shutdown, deadline.into()).await
+    }
+}
+
+#[cfg(all(test, feature = "tokio"))]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn test_async_handlers() -> crate::Result<()> {
+        let mut listener = AsyncEventListener::new();
+        let calls = Arc::new(AtomicUsize::new(0));
+
+        let counter = calls.clone();
+        let guard = listener.guard(listener.add_layer_opened_handler(move |_| {
+            let counter = counter.clone();
+            async move {
+                counter.fetch_add(1, Ordering::Relaxed);
             }
-            let buf = &buffer[..bytes_read];
-            let string = String::from_utf8_lossy(buf);
-            let parsed: Vec<Event> = event_parser(&string)?;
-            for event in parsed {
-                self.event_primer_exec_async(event, &mut active_windows)
-                    .await?;
+        }));
+        let counter = calls.clone();
+        listener.add_layer_opened_handler_once(move |_| {
+            let counter = counter.clone();
+            Box::pin(async move {
+                counter.fetch_add(10, Ordering::Relaxed);
+            })
+        });
+
+        let mut active_windows = vec![];
+        for event in event_parser("openlayer>>waybar\nopenlayer>>waybar")? {
+            listener
+                .event_primer_exec_async(event, &mut active_windows)
+                .await?;
+        }
+        assert_eq!(calls.load(Ordering::Relaxed), 12);
+
+        drop(guard);
+        for event in event_parser("openlayer>>waybar")? {
+            listener
+                .event_primer_exec_async(event, &mut active_windows)
+                .await?;
+        }
+        assert_eq!(calls.load(Ordering::Relaxed), 12);
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn test_async_closure_shim() -> crate::Result<()> {
+        use hyprland_macros::async_closure;
+        static NAMES: Mutex<Vec<String>> = Mutex::new(vec![]);
+        let listener = AsyncEventListener::new();
+
+        listener.add_sub_map_changed_handler(async_closure! {
+            |name| NAMES.lock().unwrap_or_else(|e| e.into_inner()).push(format!("shim {name}"))
+        });
+        listener.add_sub_map_changed_handler(async |name| {
+            let mut names = NAMES.lock().unwrap_or_else(|e| e.into_inner());
+            names.push(format!("native {name}"));
+        });
+        let prefix = "cloned".to_string();
+        listener.add_sub_map_changed_handler(move |name| {
+            let prefix = prefix.clone();
+            async move {
+                let mut names = NAMES.lock().unwrap_or_else(|e| e.into_inner());
+                names.push(format!("{prefix} {name}"));
             }
+        });
+        listener.add_config_reloaded_handler(async || {
+            let mut names = NAMES.lock().unwrap_or_else(|e| e.into_inner());
+            names.push("reloaded".to_string());
+        });
+
+        let mut listener = listener;
+        let mut active_windows = vec![];
+        for event in event_parser("submap>>resize\nconfigreloaded>>")? {
+            listener
+                .event_primer_exec_async(event, &mut active_windows)
+                .await?;
         }
+        assert_eq!(
+            *NAMES.lock().unwrap_or_else(|e| e.into_inner()),
+            ["shim resize", "native resize", "cloned resize", "reloaded"]
+        );
         Ok(())
     }
 }
@@ -0,0 +1,218 @@
+use super::*;
+use crate::error::hypr_err;
+use crate::instance::Instance;
+use std::collections::VecDeque;
+use std::os::unix::net::UnixStream;
+use std::sync::mpsc;
+
+/// A blocking [Iterator] over the events of an instance, see [Instance::events_blocking]
+///
+/// Active window events are merged the same way as in [EventListener]
+///
+/// ```rust, no_run
+/// use hyprland::default_instance;
+/// for event in default_instance()?.events_blocking() {
+///     println!("{:?}", event?);
+/// }
+/// # Ok::<(), hyprland::error::HyprError>(())
+/// ```
+pub struct BlockingEvents {
+    instance: Option<Instance>,
+    stream: Option<UnixStream>,
+    shutdown: ShutdownHandle,
+    lines: EventLineBuffer,
+    kinds: KindSet,
+    active_windows: Vec<ActiveWindowState>,
+    pending: VecDeque<Event>,
+    done: bool,
+}
+
+impl BlockingEvents {
+    pub(crate) fn new(instance: Instance) -> Self {
+        Self {
+            instance: Some(instance),
+            stream: None,
+            shutdown: ShutdownHandle::new(),
+            lines: EventLineBuffer::new(),
+            kinds: KindSet::DEFAULT,
+            active_windows: vec![],
+            pending: VecDeque::new(),
+            done: false,
+        }
+    }
+
+    #[cfg(test)]
+    fn from_stream(stream: UnixStream) -> crate::Result<Self> {
+        let shutdown = ShutdownHandle::new();
+        shutdown.register_stream(&stream)?;
+        Ok(Self {
+            instance: None,
+            stream: Some(stream),
+            shutdown,
+            lines: EventLineBuffer::new(),
+            kinds: KindSet::DEFAULT,
+            active_windows: vec![],
+            pending: VecDeque::new(),
+            done: false,
+        })
+    }
+
+    /// Returns a handle that ends the iterator from another thread
+    pub fn shutdown_handle(&self) -> ShutdownHandle {
+        self.shutdown.clone()
+    }
+
+    /// Only yields events of the given kinds
+    ///
+    /// The other events are skipped right after being parsed, before any other work is done on them.
+    /// Without a filter every event but the legacy ones is yielded, see [EventKind::is_legacy]
+    pub fn filter_kinds(mut self, kinds: &[EventKind]) -> Self {
+        self.kinds = KindSet::from_kinds(kinds);
+        self
+    }
+
+    /// Reads the events on a background thread, sending them over a channel
+    ///
+    /// The thread stops when the socket closes, after an error, once the returned
+    /// [ShutdownHandle] is used, or after the receiver is dropped and another event arrives
+    ///
+    /// ```rust, no_run
+    /// use hyprland::default_instance;
+    /// let (events, shutdown) = default_instance()?.events_blocking().spawn()?;
+    /// for event in events.iter().take(10) {
+    ///     println!("{:?}", event?);
+    /// }
+    /// shutdown.shutdown();
+    /// # Ok::<(), hyprland::error::HyprError>(())
+    /// ```
+    pub fn spawn(self) -> crate::Result<(mpsc::Receiver<crate::Result<Event>>, ShutdownHandle)> {
+        let (sender, receiver) = mpsc::channel();
+        let shutdown = self.shutdown_handle();
+        std::thread::Builder::new()
+            .name("hyprland-events".to_string())
+            .spawn(move || {
+                for event in self {
+                    if sender.send(event).is_err() {
+                        break;
+                    }
+                }
+            })?;
+        Ok((receiver, shutdown))
+    }
+
+    /// Reads from the socket until at least one event is pending or the iterator ends
+    fn fill(&mut self) -> crate::Result<()> {
+        let stream = match &mut self.stream {
+            Some(stream) => stream,
+            None => {
+                let Some(instance) = &self.instance else {
+                    hypr_err!("the event iterator has no socket to read from");
+                };
+                let stream = instance.get_event_stream()?;
+                self.shutdown.register_stream(&stream)?;
+                self.stream.insert(stream)
+            }
+        };
+        let mut buffer = [0; 4096];
+        let Some(bytes_read) = read_blocking(stream, &mut buffer, &self.shutdown, None)? else {
+            self.done = true;
+            return Ok(());
+        };
+        for event in parse_lines(self.lines.push(&buffer[..bytes_read]))? {
+            if !self.kinds.contains(event.kind()) {
+                continue;
+            }
+            self.pending
+                .extend(event_primer_noexec(event, &mut self.active_windows)?);
+        }
+        if bytes_read == 0 {
+            // If no bytes were read, we can assume the stream is closed
+            self.done = true;
+        }
+        Ok(())
+    }
+}
+
+impl Iterator for BlockingEvents {
+    type Item = crate::Result<Event>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.shutdown.is_shutdown() {
+                return None;
+            }
+            if let Some(event) = self.pending.pop_front() {
+                return Some(Ok(event));
+            }
+            if self.done {
+                return None;
+            }
+            if let Err(e) = self.fill() {
+                self.done = true;
+                return Some(Err(e));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn test_blocking_events() -> crate::Result<()> {
+        let (stream, mut other) = UnixStream::pair()?;
+        let events = BlockingEvents::from_stream(stream)?;
+        other.write_all(b"activewindow>>kitty,~\nactivewindowv2>>5d3a1f80\nsub")?;
+        other.write_all(b"map>>resize\n")?;
+        drop(other);
+        let events = events.collect::<crate::Result<Vec<_>>>()?;
+        assert_eq!(
+            events,
+            [
+                Event::ActiveWindowChanged(Some(WindowEventData {
+                    class: "kitty".to_string(),
+                    title: "~".to_string(),
+                    address: crate::shared::Address::new("5d3a1f80"),
+                })),
+                Event::SubMapChanged("resize".to_string()),
+            ]
+        );
+
+        // the legacy windowtitle event is skipped unless asked for
+        let titles = b"windowtitle>>5d3a1f80\nwindowtitlev2>>5d3a1f80,vim\n";
+        let (stream, mut other) = UnixStream::pair()?;
+        let events = BlockingEvents::from_stream(stream)?;
+        other.write_all(titles)?;
+        drop(other);
+        let kinds = events
+            .map(|event| event.map(|event| event.kind()))
+            .collect::<crate::Result<Vec<_>>>()?;
+        assert_eq!(kinds, [EventKind::WindowTitleChanged]);
+        let (stream, mut other) = UnixStream::pair()?;
+        let events =
+            BlockingEvents::from_stream(stream)?.filter_kinds(&[EventKind::WindowTitleChangedV1]);
+        other.write_all(titles)?;
+        drop(other);
+        let events = events.collect::<crate::Result<Vec<_>>>()?;
+        assert_eq!(
+            events,
+            [Event::WindowTitleChangedV1(crate::shared::Address::new(
+                "5d3a1f80"
+            ))]
+        );
+
+        let (stream, mut other) = UnixStream::pair()?;
+        let events = BlockingEvents::from_stream(stream)?;
+        other.write_all(b"configreloaded>>\n")?;
+        let (receiver, shutdown) = events.spawn()?;
+        match receiver.recv() {
+            Ok(event) => assert_eq!(event?, Event::ConfigReloaded),
+            Err(e) => panic!("Error occured: {e}"),
+        }
+        shutdown.shutdown();
+        assert!(receiver.recv().is_err());
+        Ok(())
+    }
+}
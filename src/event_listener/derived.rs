@@ -0,0 +1,579 @@
+use super::*;
+use crate::async_import::sleep;
+use crate::shared::Address;
+use futures_lite::{Stream, StreamExt, future};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+/// A higher level event derived from several [Event]s, see [DerivedEventsExt::derive_events]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum DerivedEvent {
+    /// The focus changed, one event for a burst of [Event::ActiveWindowChanged],
+    /// [Event::WorkspaceChanged] and [Event::ActiveMonitorChanged]
+    FocusChanged(FocusChange),
+    /// The last window of a workspace was closed or moved away, with the workspace name
+    WorkspaceBecameEmpty(String),
+    /// Monitors were added or removed, and no more changes followed for
+    /// [DeriveOptions::hotplug_settle]
+    MonitorHotplugSettled(MonitorHotplugData),
+    /// A window has been focused for [DeriveOptions::focused_for], it is emitted once for
+    /// every time the window gets focused
+    WindowFocusedFor(WindowEventData, Duration),
+}
+
+/// The focus after a burst of focus events, see [DerivedEvent::FocusChanged]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FocusChange {
+    /// The active window, [None] if there is none
+    pub window: Option<WindowEventData>,
+    /// The active workspace, [None] until it first changes
+    pub workspace: Option<WorkspaceEventData>,
+    /// The name of the active monitor, [None] until it first changes
+    pub monitor: Option<String>,
+}
+
+/// The monitors of a settled hotplug, see [DerivedEvent::MonitorHotplugSettled]
+///
+/// A monitor unplugged and plugged back in is in both lists
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MonitorHotplugData {
+    /// The names of the added monitors
+    pub added: Vec<String>,
+    /// The names of the removed monitors
+    pub removed: Vec<String>,
+}
+
+/// The settings of [DerivedEventsExt::derive_events]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeriveOptions {
+    /// How long after the first focus event others are coalesced into the same
+    /// [DerivedEvent::FocusChanged]
+    pub focus_burst: Duration,
+    /// How long no monitor may be added or removed before [DerivedEvent::MonitorHotplugSettled]
+    pub hotplug_settle: Duration,
+    /// How long a window has to stay focused for [DerivedEvent::WindowFocusedFor]
+    pub focused_for: Duration,
+    /// The windows open before the stream started, with the names of their workspaces.
+    /// Only these and the windows opened later count for [DerivedEvent::WorkspaceBecameEmpty]
+    pub windows: Vec<(Address, String)>,
+}
+
+impl Default for DeriveOptions {
+    fn default() -> Self {
+        Self {
+            focus_burst: Duration::from_millis(50),
+            hotplug_settle: Duration::from_secs(1),
+            focused_for: Duration::from_secs(1),
+            windows: vec![],
+        }
+    }
+}
+
+/// Stream combinators deriving higher level events, for any [Stream] of [Event]s like
+/// [EventStream] or [Subscription][crate::event_listener::Subscription]
+///
+/// Errors from the stream are passed through right away
+///
+/// ```rust, no_run
+/// use hyprland::data::Clients;
+/// use hyprland::event_listener::{DeriveOptions, DerivedEventsExt, EventStream};
+/// use hyprland::prelude::*;
+/// use futures_lite::StreamExt;
+/// use std::time::Duration;
+///
+/// #[tokio::main(flavor = "current_thread")]
+/// async fn main() -> hyprland::Result<()> {
+///     let windows = Clients::get_async().await?.into_iter();
+///     let options = DeriveOptions {
+///         focused_for: Duration::from_secs(3),
+///         windows: windows.map(|c| (c.address, c.workspace.name)).collect(),
+///         ..DeriveOptions::default()
+///     };
+///     let mut stream = EventStream::new().derive_events(options);
+///     while let Some(event) = stream.next().await {
+///         println!("{:?}", event?);
+///     }
+///     Ok(())
+/// }
+/// ```
+pub trait DerivedEventsExt: Stream<Item = crate::Result<Event>> + Sized + Send + 'static {
+    /// Holds back the events `key` returns a key for, until no event with the same key followed
+    /// for `window`, then yields the last one. Events without a key are yielded right away,
+    /// so they can overtake held back events
+    fn debounce_by<K, F>(self, window: Duration, key: F) -> DerivedStream<Event>
+    where
+        K: PartialEq + Send + 'static,
+        F: FnMut(&Event) -> Option<K> + Send + 'static,
+    {
+        DerivedStream::new(self, Debouncer::new(window, key))
+    }
+
+    /// Debounces title changes per window, see [Self::debounce_by]
+    fn debounce_titles(self, window: Duration) -> DerivedStream<Event> {
+        self.debounce_by(window, |event| match event {
+            Event::WindowTitleChanged(data) => Some((event.kind(), data.address.clone())),
+            Event::WindowTitleChangedV1(address) => Some((event.kind(), address.clone())),
+            _ => None,
+        })
+    }
+
+    /// Yields the [DerivedEvent]s of the stream, dropping the events themselves
+    fn derive_events(self, options: DeriveOptions) -> DerivedStream<DerivedEvent> {
+        DerivedStream::new(self, Deriver::new(options))
+    }
+}
+
+impl<S: Stream<Item = crate::Result<Event>> + Send + 'static> DerivedEventsExt for S {}
+
+/// A [Stream] made by the combinators of [DerivedEventsExt]
+#[must_use = "streams nothing unless polled"]
+pub struct DerivedStream<T> {
+    stream: Pin<Box<dyn Stream<Item = crate::Result<T>> + Send>>,
+}
+
+impl<T: Send + 'static> DerivedStream<T> {
+    fn new(
+        events: impl Stream<Item = crate::Result<Event>> + Send + 'static,
+        mut state: impl TimedState<Output = T>,
+    ) -> Self {
+        let stream = async_stream::stream! {
+            let mut events = Box::pin(events);
+            let mut out = VecDeque::new();
+            loop {
+                // None when the deadline passed before the next event
+                let next = match state.deadline() {
+                    Some(deadline) => {
+                        let wait = deadline.saturating_duration_since(Instant::now());
+                        future::or(async { Some(events.next().await) }, async {
+                            sleep(wait).await;
+                            None
+                        })
+                        .await
+                    }
+                    None => Some(events.next().await),
+                };
+                let ended = matches!(next, Some(None));
+                match next {
+                    Some(Some(Ok(event))) => state.on_event(event, Instant::now(), &mut out),
+                    Some(Some(Err(e))) => yield Err(e),
+                    Some(None) => state.finish(&mut out),
+                    None => state.on_tick(Instant::now(), &mut out),
+                }
+                for derived in out.drain(..) {
+                    yield Ok(derived);
+                }
+                if ended {
+                    break;
+                }
+            }
+        };
+        Self {
+            stream: Box::pin(stream),
+        }
+    }
+}
+
+impl<T> Stream for DerivedStream<T> {
+    type Item = crate::Result<T>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.as_mut().stream.poll_next(cx)
+    }
+}
+
+/// The state of a combinator, fed events and woken up at its deadline
+trait TimedState: Send + 'static {
+    type Output;
+
+    fn on_event(&mut self, event: Event, now: Instant, out: &mut VecDeque<Self::Output>);
+
+    fn on_tick(&mut self, now: Instant, out: &mut VecDeque<Self::Output>);
+
+    /// When [Self::on_tick] has to run next
+    fn deadline(&self) -> Option<Instant>;
+
+    /// Runs when the stream ended
+    fn finish(&mut self, out: &mut VecDeque<Self::Output>);
+}
+
+struct Debouncer<K, F> {
+    window: Duration,
+    key: F,
+    // ordered by deadline, as every event moves its key to the back
+    pending: VecDeque<(K, Event, Instant)>,
+}
+
+impl<K, F> Debouncer<K, F> {
+    fn new(window: Duration, key: F) -> Self {
+        Self {
+            window,
+            key,
+            pending: VecDeque::new(),
+        }
+    }
+}
+
+impl<K, F> TimedState for Debouncer<K, F>
+where
+    K: PartialEq + Send + 'static,
+    F: FnMut(&Event) -> Option<K> + Send + 'static,
+{
+    type Output = Event;
+
+    fn on_event(&mut self, event: Event, now: Instant, out: &mut VecDeque<Event>) {
+        let Some(key) = (self.key)(&event) else {
+            out.push_back(event);
+            return;
+        };
+        self.pending.retain(|(pending, ..)| *pending != key);
+        self.pending.push_back((key, event, now + self.window));
+    }
+
+    fn on_tick(&mut self, now: Instant, out: &mut VecDeque<Event>) {
+        while let Some((_, _, deadline)) = self.pending.front() {
+            if *deadline > now {
+                break;
+            }
+            if let Some((_, event, _)) = self.pending.pop_front() {
+                out.push_back(event);
+            }
+        }
+    }
+
+    fn deadline(&self) -> Option<Instant> {
+        self.pending.front().map(|(_, _, deadline)| *deadline)
+    }
+
+    fn finish(&mut self, out: &mut VecDeque<Event>) {
+        out.extend(self.pending.drain(..).map(|(_, event, _)| event));
+    }
+}
+
+struct Deriver {
+    options: DeriveOptions,
+    focus: FocusChange,
+    last_focus: Option<FocusChange>,
+    focus_deadline: Option<Instant>,
+    focused: Option<Address>,
+    dwell: Option<(WindowEventData, Instant)>,
+    windows: Vec<(Address, String)>,
+    hotplug: Option<(MonitorHotplugData, Instant)>,
+}
+
+impl Deriver {
+    fn new(mut options: DeriveOptions) -> Self {
+        let windows = std::mem::take(&mut options.windows);
+        Self {
+            options,
+            focus: FocusChange::default(),
+            last_focus: None,
+            focus_deadline: None,
+            focused: None,
+            dwell: None,
+            windows,
+            hotplug: None,
+        }
+    }
+
+    fn focus_event(&mut self, now: Instant) {
+        self.focus_deadline
+            .get_or_insert(now + self.options.focus_burst);
+    }
+
+    fn emit_focus(&mut self, out: &mut VecDeque<DerivedEvent>) {
+        self.focus_deadline = None;
+        if self.last_focus.as_ref() != Some(&self.focus) {
+            self.last_focus = Some(self.focus.clone());
+            out.push_back(DerivedEvent::FocusChanged(self.focus.clone()));
+        }
+    }
+
+    /// Moves a window to another workspace, [None] when it closed
+    fn window_moved(
+        &mut self,
+        address: &Address,
+        workspace: Option<String>,
+        out: &mut VecDeque<DerivedEvent>,
+    ) {
+        let old = match self
+            .windows
+            .iter()
+            .position(|(window, _)| window == address)
+        {
+            Some(index) => Some(self.windows.swap_remove(index).1),
+            None => None,
+        };
+        if let Some(workspace) = workspace {
+            self.windows.push((address.clone(), workspace));
+        }
+        let empty = old.filter(|old| !self.windows.iter().any(|(_, workspace)| workspace == old));
+        if let Some(old) = empty {
+            out.push_back(DerivedEvent::WorkspaceBecameEmpty(old));
+        }
+    }
+
+    fn hotplug(&mut self, now: Instant, name: &str, added: bool) {
+        let deadline = now + self.options.hotplug_settle;
+        let (data, settle) = self
+            .hotplug
+            .get_or_insert_with(|| (MonitorHotplugData::default(), deadline));
+        *settle = deadline;
+        let names = if added {
+            &mut data.added
+        } else {
+            &mut data.removed
+        };
+        if !names.iter().any(|known| known == name) {
+            names.push(name.to_string());
+        }
+    }
+}
+
+impl TimedState for Deriver {
+    type Output = DerivedEvent;
+
+    fn on_event(&mut self, event: Event, now: Instant, out: &mut VecDeque<DerivedEvent>) {
+        match event {
+            Event::ActiveWindowChanged(window) => {
+                let address = window.as_ref().map(|window| window.address.clone());
+                if address != self.focused {
+                    self.focused = address;
+                    self.dwell = window
+                        .clone()
+                        .map(|window| (window, now + self.options.focused_for));
+                }
+                self.focus.window = window;
+                self.focus_event(now);
+            }
+            Event::WorkspaceChanged(workspace) => {
+                self.focus.workspace = Some(workspace);
+                self.focus_event(now);
+            }
+            Event::ActiveMonitorChanged(monitor) => {
+                self.focus.monitor = Some(monitor.monitor_name);
+                self.focus_event(now);
+            }
+            Event::WindowOpened(window) => {
+                self.window_moved(&window.window_address, Some(window.workspace_name), out);
+            }
+            Event::WindowMoved(WindowMoveEvent {
+                window_address,
+                workspace_name,
+                ..
+            }) => self.window_moved(&window_address, Some(workspace_name.to_string()), out),
+            Event::WindowClosed(address) => self.window_moved(&address, None, out),
+            Event::MonitorAdded(MonitorAddedEventData { name, .. }) => {
+                self.hotplug(now, &name, true);
+            }
+            Event::MonitorRemovedV2(MonitorAddedEventData { name, .. })
+            | Event::MonitorRemoved(name) => self.hotplug(now, &name, false),
+            _ => {}
+        }
+    }
+
+    fn on_tick(&mut self, now: Instant, out: &mut VecDeque<DerivedEvent>) {
+        if self.focus_deadline.is_some_and(|deadline| deadline <= now) {
+            self.emit_focus(out);
+        }
+        if let Some((window, _)) = self.dwell.take_if(|(_, deadline)| *deadline <= now) {
+            out.push_back(DerivedEvent::WindowFocusedFor(
+                window,
+                self.options.focused_for,
+            ));
+        }
+        if let Some((data, _)) = self.hotplug.take_if(|(_, deadline)| *deadline <= now) {
+            out.push_back(DerivedEvent::MonitorHotplugSettled(data));
+        }
+    }
+
+    fn deadline(&self) -> Option<Instant> {
+        [
+            self.focus_deadline,
+            self.dwell.as_ref().map(|(_, deadline)| *deadline),
+            self.hotplug.as_ref().map(|(_, deadline)| *deadline),
+        ]
+        .into_iter()
+        .flatten()
+        .min()
+    }
+
+    fn finish(&mut self, out: &mut VecDeque<DerivedEvent>) {
+        if self.focus_deadline.is_some() {
+            self.emit_focus(out);
+        }
+        if let Some((data, _)) = self.hotplug.take() {
+            out.push_back(DerivedEvent::MonitorHotplugSettled(data));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::WorkspaceType;
+
+    fn window(address: &str) -> WindowEventData {
+        WindowEventData {
+            class: "kitty".to_string(),
+            title: "~".to_string(),
+            address: Address::new(address),
+        }
+    }
+
+    fn run<S: TimedState>(state: &mut S, steps: Vec<(u64, Option<Event>)>) -> Vec<S::Output> {
+        let start = Instant::now();
+        let mut out = VecDeque::new();
+        for (ms, event) in steps {
+            let now = start + Duration::from_millis(ms);
+            while let Some(deadline) = state.deadline().filter(|deadline| *deadline <= now) {
+                state.on_tick(deadline, &mut out);
+            }
+            match event {
+                Some(event) => state.on_event(event, now, &mut out),
+                None => state.on_tick(now, &mut out),
+            }
+        }
+        state.finish(&mut out);
+        out.into()
+    }
+
+    #[test]
+    fn test_debounce() {
+        let title = |address: &str, title: &str| {
+            Event::WindowTitleChanged(WindowTitleEventData {
+                address: Address::new(address),
+                title: title.to_string(),
+            })
+        };
+        let mut debouncer =
+            Debouncer::new(Duration::from_millis(100), |event: &Event| match event {
+                Event::WindowTitleChanged(data) => Some(data.address.clone()),
+                _ => None,
+            });
+        let events = run(
+            &mut debouncer,
+            vec![
+                (0, Some(title("1", "a"))),
+                (10, Some(title("2", "x"))),
+                (50, Some(title("1", "ab"))),
+                (60, Some(Event::ConfigReloaded)),
+                (120, None),
+                (200, Some(title("1", "abc"))),
+            ],
+        );
+        assert_eq!(
+            events,
+            [
+                Event::ConfigReloaded,
+                title("2", "x"),
+                title("1", "ab"),
+                title("1", "abc")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_derived_events() {
+        let workspace = |id: i32| {
+            Event::WorkspaceChanged(WorkspaceEventData {
+                name: WorkspaceType::Regular(id.to_string()),
+                id,
+            })
+        };
+        let monitor = |name: &str| MonitorAddedEventData {
+            id: 1,
+            name: name.to_string(),
+            description: String::new(),
+        };
+        let mut deriver = Deriver::new(DeriveOptions {
+            windows: vec![(Address::new("1"), "1".to_string())],
+            ..DeriveOptions::default()
+        });
+        let events = run(
+            &mut deriver,
+            vec![
+                (0, Some(workspace(2))),
+                (5, Some(Event::ActiveWindowChanged(Some(window("2"))))),
+                (10, Some(Event::ActiveWindowChanged(Some(window("2"))))),
+                (100, Some(Event::WindowClosed(Address::new("1")))),
+                (
+                    200,
+                    Some(Event::WindowOpened(WindowOpenEvent {
+                        window_address: Address::new("3"),
+                        workspace_name: "2".to_string(),
+                        window_class: "kitty".to_string(),
+                        window_title: "~".to_string(),
+                    })),
+                ),
+                (300, Some(Event::MonitorRemoved("DP-1".to_string()))),
+                (400, Some(Event::MonitorAdded(monitor("DP-1")))),
+                (800, Some(Event::ActiveWindowChanged(None))),
+                (900, Some(Event::MonitorAdded(monitor("HDMI-A-1")))),
+                (2000, None),
+            ],
+        );
+        assert_eq!(
+            events,
+            [
+                DerivedEvent::FocusChanged(FocusChange {
+                    window: Some(window("2")),
+                    workspace: Some(WorkspaceEventData {
+                        name: WorkspaceType::Regular("2".to_string()),
+                        id: 2
+                    }),
+                    monitor: None,
+                }),
+                DerivedEvent::WorkspaceBecameEmpty("1".to_string()),
+                DerivedEvent::FocusChanged(FocusChange {
+                    window: None,
+                    workspace: Some(WorkspaceEventData {
+                        name: WorkspaceType::Regular("2".to_string()),
+                        id: 2
+                    }),
+                    monitor: None,
+                }),
+                DerivedEvent::MonitorHotplugSettled(MonitorHotplugData {
+                    added: vec!["DP-1".to_string(), "HDMI-A-1".to_string()],
+                    removed: vec!["DP-1".to_string()],
+                }),
+            ]
+        );
+
+        // the window stays focused long enough this time
+        let events = run(
+            &mut Deriver::new(DeriveOptions::default()),
+            vec![
+                (0, Some(Event::ActiveWindowChanged(Some(window("4"))))),
+                (1500, None),
+            ],
+        );
+        assert_eq!(
+            events[1],
+            DerivedEvent::WindowFocusedFor(window("4"), Duration::from_secs(1))
+        );
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test(flavor = "current_thread")]
+    async fn test_derived_stream() -> crate::Result<()> {
+        let capture = "windowtitlev2>>1,a\nwindowtitlev2>>1,ab\nsubmap>>resize\n";
+        let events = EventStream::replay_raw(std::io::Cursor::new(capture))
+            .debounce_titles(Duration::from_millis(20))
+            .try_collect::<_, _, Vec<_>>()
+            .await?;
+        assert_eq!(
+            events,
+            [
+                Event::SubMapChanged("resize".to_string()),
+                Event::WindowTitleChanged(WindowTitleEventData {
+                    address: Address::new("1"),
+                    title: "ab".to_string(),
+                }),
+            ]
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,367 @@
+use super::*;
+use crate::data::{Client, Clients, Workspace, Workspaces};
+use crate::default_instance;
+use crate::instance::Instance;
+use crate::shared::{Address, HyprData, HyprDataVec, WorkspaceId};
+use futures_lite::{Stream, StreamExt};
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// An [Event] with the client and workspace it refers to, see [EventEnricher]
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnrichedEvent {
+    /// The event
+    pub event: Event,
+    /// The client the event refers to, for [Event::WindowClosed] its last known state
+    pub client: Option<Client>,
+    /// The workspace the event refers to, or the workspace of its client.
+    /// For [Event::WorkspaceDeleted] its last known state
+    pub workspace: Option<Workspace>,
+}
+
+/// Resolves the addresses and workspace ids of events into [Client]s and [Workspace]s
+///
+/// Lookups are served from a cache, which is only refetched on a miss or on events changing
+/// the state of a window or workspace, titles are taken from the events themselves.
+/// Closed windows and deleted workspaces keep their last known state until their event,
+/// entries missing from two fetches in a row are dropped as their event was lost.
+/// Workspaces are only resolved by id, so the V1 events carrying just a name have no workspace
+///
+/// ```rust, no_run
+/// use hyprland::event_listener::EventStream;
+/// use futures_lite::StreamExt;
+///
+/// #[tokio::main(flavor = "current_thread")]
+/// async fn main() -> hyprland::Result<()> {
+///     let mut stream = EventStream::new().enrich();
+///     while let Some(enriched) = stream.next().await {
+///         let enriched = enriched?;
+///         if let Some(client) = enriched.client {
+///             println!("{:?} from pid {}", enriched.event.kind(), client.pid);
+///         }
+///     }
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct EventEnricher {
+    instance: Option<Instance>,
+    clients: Cache<Address, Client>,
+    workspaces: Cache<WorkspaceId, Workspace>,
+}
+
+impl EventEnricher {
+    /// Creates a new [EventEnricher]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new [EventEnricher]
+    pub fn instance_new(instance: Instance) -> Self {
+        Self {
+            instance: Some(instance),
+            ..Self::default()
+        }
+    }
+
+    /// Returns the cached state of a client
+    pub fn client(&self, address: &Address) -> Option<&Client> {
+        self.clients.entries.get(address)
+    }
+
+    /// Returns the cached state of a workspace
+    pub fn workspace(&self, id: WorkspaceId) -> Option<&Workspace> {
+        self.workspaces.entries.get(&id)
+    }
+
+    /// Attaches the client and workspace an event refers to
+    pub async fn enrich(&mut self, event: Event) -> crate::Result<EnrichedEvent> {
+        let (address, workspace_id) = references(&event);
+        let changed = changes_state(&event);
+        let fetch_clients = !matches!(event, Event::WindowClosed(_))
+            && address.is_some_and(|address| changed || self.client(address).is_none());
+        if fetch_clients {
+            let clients = Clients::instance_get_async(self.instance()?).await?;
+            self.clients.update(
+                clients
+                    .to_vec()
+                    .into_iter()
+                    .map(|client| (client.address.clone(), client)),
+            );
+        }
+        let workspace_id =
+            workspace_id.or_else(|| self.client(address?).map(|client| client.workspace.id));
+        let fetch_workspaces =
+            !matches!(event, Event::WindowClosed(_) | Event::WorkspaceDeleted(_))
+                && workspace_id.is_some_and(|id| changed || self.workspace(id).is_none());
+        if fetch_workspaces {
+            let workspaces = Workspaces::instance_get_async(self.instance()?).await?;
+            self.workspaces.update(
+                workspaces
+                    .to_vec()
+                    .into_iter()
+                    .map(|workspace| (workspace.id, workspace)),
+            );
+        }
+        Ok(self.resolve(event))
+    }
+
+    fn instance(&self) -> crate::Result<&Instance> {
+        match &self.instance {
+            Some(instance) => Ok(instance),
+            None => default_instance(),
+        }
+    }
+
+    /// Looks the event up in the cache, forgetting closed windows and deleted workspaces
+    fn resolve(&mut self, event: Event) -> EnrichedEvent {
+        let (address, workspace_id) = references(&event);
+        let client = match (&event, address) {
+            (Event::WindowClosed(address), _) => self.clients.remove(address),
+            (Event::WindowTitleChanged(data), _) => {
+                self.clients.entries.get_mut(&data.address).map(|client| {
+                    client.title.clone_from(&data.title);
+                    client.clone()
+                })
+            }
+            (_, Some(address)) => self.client(address).cloned(),
+            (_, None) => None,
+        };
+        let workspace = match (&event, workspace_id) {
+            (Event::WorkspaceDeleted(data), _) => self.workspaces.remove(&data.id),
+            (_, Some(id)) => self.workspace(id).cloned(),
+            (_, None) => client
+                .as_ref()
+                .and_then(|client| self.workspace(client.workspace.id).cloned()),
+        };
+        EnrichedEvent {
+            event,
+            client,
+            workspace,
+        }
+    }
+}
+
+/// Cached entries, with the ones missing from the last fetch
+#[derive(Debug, Clone)]
+struct Cache<K, V> {
+    entries: HashMap<K, V>,
+    missing: HashSet<K>,
+}
+
+impl<K, V> Default for Cache<K, V> {
+    fn default() -> Self {
+        Self {
+            entries: HashMap::new(),
+            missing: HashSet::new(),
+        }
+    }
+}
+
+impl<K: Eq + Hash + Clone, V> Cache<K, V> {
+    /// Replaces the entries with a fresh fetch
+    ///
+    /// An entry missing from it is kept as its closing event may still be queued,
+    /// but dropped when the next fetch is missing it too
+    fn update(&mut self, fresh: impl IntoIterator<Item = (K, V)>) {
+        let fresh: HashMap<K, V> = fresh.into_iter().collect();
+        let missing = &self.missing;
+        self.entries
+            .retain(|key, _| fresh.contains_key(key) || !missing.contains(key));
+        self.missing = self
+            .entries
+            .keys()
+            .filter(|key| !fresh.contains_key(key))
+            .cloned()
+            .collect();
+        self.entries.extend(fresh);
+    }
+
+    fn remove(&mut self, key: &K) -> Option<V> {
+        self.missing.remove(key);
+        self.entries.remove(key)
+    }
+}
+
+/// Whether an event changes the state of its window or workspace beyond what it carries
+fn changes_state(event: &Event) -> bool {
+    matches!(
+        event,
+        Event::WindowOpened(_)
+            | Event::WindowMoved(_)
+            | Event::WindowMovedV1(_)
+            | Event::FloatStateChanged(_)
+            | Event::WindowPinned(_)
+            | Event::WindowMinimized(_)
+            | Event::WindowMovedIntoGroup(_)
+            | Event::WindowMovedOutOfGroup(_)
+            | Event::WorkspaceAdded(_)
+            | Event::WorkspaceMoved(_)
+            | Event::WorkspaceRenamed(_)
+    )
+}
+
+/// The window address and workspace id an event refers to
+fn references(event: &Event) -> (Option<&Address>, Option<WorkspaceId>) {
+    match event {
+        Event::WindowOpened(data) => (Some(&data.window_address), None),
+        Event::WindowMoved(data) => (Some(&data.window_address), Some(data.workspace_id)),
+        Event::WindowMovedV1(data) => (Some(&data.window_address), None),
+        Event::ActiveWindowChanged(data) => (data.as_ref().map(|data| &data.address), None),
+        Event::FloatStateChanged(WindowFloatEventData { address, .. })
+        | Event::WindowTitleChanged(WindowTitleEventData { address, .. })
+        | Event::WindowPinned(WindowPinEventData { address, .. })
+        | Event::WindowMinimized(WindowMinimizeEventData { address, .. })
+        | Event::WindowClosed(address)
+        | Event::UrgentStateChanged(address)
+        | Event::WindowTitleChangedV1(address)
+        | Event::WindowMovedIntoGroup(address)
+        | Event::WindowMovedOutOfGroup(address)
+        | Event::Bell(Some(address)) => (Some(address), None),
+        Event::WorkspaceChanged(WorkspaceEventData { id, .. })
+        | Event::WorkspaceAdded(WorkspaceEventData { id, .. })
+        | Event::WorkspaceDeleted(WorkspaceEventData { id, .. })
+        | Event::WorkspaceMoved(WorkspaceMovedEventData { id, .. })
+        | Event::WorkspaceRenamed(NonSpecialWorkspaceEventData { id, .. }) => (None, Some(*id)),
+        Event::ActiveMonitorChangedV2(data) => (None, data.workspace_id),
+        Event::ActiveSpecialChanged(data) => (None, data.workspace.as_ref().map(|ws| ws.id)),
+        _ => (None, None),
+    }
+}
+
+/// A [Stream] of [EnrichedEvent]s, see [EventStream::enrich]
+#[must_use = "streams nothing unless polled"]
+pub struct EnrichedEventStream {
+    stream: Pin<Box<dyn Stream<Item = crate::Result<EnrichedEvent>> + Send>>,
+}
+
+impl EnrichedEventStream {
+    /// Returns the events of `stream` enriched by `enricher`
+    ///
+    /// An error fetching the clients or workspaces is yielded in place of the event, and the
+    /// stream goes on with the next event
+    pub fn new(
+        stream: impl Stream<Item = crate::Result<Event>> + Send + 'static,
+        mut enricher: EventEnricher,
+    ) -> Self {
+        let stream = async_stream::stream! {
+            let mut stream = Box::pin(stream);
+            while let Some(event) = stream.next().await {
+                match event {
+                    Ok(event) => yield enricher.enrich(event).await,
+                    Err(e) => yield Err(e),
+                }
+            }
+        };
+        Self {
+            stream: Box::pin(stream),
+        }
+    }
+}
+
+impl Stream for EnrichedEventStream {
+    type Item = crate::Result<EnrichedEvent>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.as_mut().stream.poll_next(cx)
+    }
+}
+
+impl EventStream {
+    /// Attaches the clients and workspaces the events refer to, see [EventEnricher]
+    pub fn enrich(self) -> EnrichedEventStream {
+        EnrichedEventStream::new(self, EventEnricher::new())
+    }
+
+    /// Attaches the clients and workspaces the events refer to, see [EventEnricher]
+    pub fn instance_enrich(self, instance: Instance) -> EnrichedEventStream {
+        EnrichedEventStream::new(self, EventEnricher::instance_new(instance))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn client(address: &str, workspace: WorkspaceId) -> crate::Result<Client> {
+        Ok(serde_json::from_value(serde_json::json!({
+            "address": address, "mapped": true, "hidden": false, "at": [0, 0], "size": [800, 600],
+            "workspace": { "id": workspace, "name": workspace.to_string() }, "floating": false,
+            "pseudo": false, "monitor": 0, "class": "kitty", "title": "~", "initialClass": "kitty",
+            "initialTitle": "~", "pid": 4242, "xwayland": false, "pinned": false, "fullscreen": 0,
+            "fullscreenClient": 0, "grouped": [], "tags": [], "swallowing": null,
+            "focusHistoryID": 0, "visible": true
+        }))?)
+    }
+
+    fn workspace(id: WorkspaceId) -> crate::Result<Workspace> {
+        Ok(serde_json::from_value(serde_json::json!({
+            "id": id, "name": id.to_string(), "monitor": "DP-1", "monitorID": 0, "windows": 1,
+            "hasfullscreen": false, "lastwindow": "0x1", "lastwindowtitle": "~"
+        }))?)
+    }
+
+    #[test]
+    fn test_enrich_cache() -> crate::Result<()> {
+        let mut enricher = EventEnricher::new();
+        enricher.clients.update([
+            (Address::new("1"), client("0x1", 2)?),
+            (Address::new("2"), client("0x2", 2)?),
+        ]);
+        enricher
+            .workspaces
+            .update([(2, workspace(2)?), (3, workspace(3)?)]);
+        // windows Hyprland no longer lists stay cached until they are closed
+        enricher
+            .clients
+            .update([(Address::new("2"), client("0x2", 2)?)]);
+        assert!(enricher.client(&Address::new("1")).is_some());
+
+        let title = enricher.resolve(Event::WindowTitleChanged(WindowTitleEventData {
+            address: Address::new("2"),
+            title: "vim".to_string(),
+        }));
+        assert_eq!(
+            title.client.map(|client| client.title),
+            Some("vim".to_string())
+        );
+        assert_eq!(
+            enricher
+                .client(&Address::new("2"))
+                .map(|c| c.title.as_str()),
+            Some("vim")
+        );
+
+        let float = enricher.resolve(Event::FloatStateChanged(WindowFloatEventData {
+            address: Address::new("1"),
+            floating: true,
+        }));
+        assert_eq!(float.client, Some(client("0x1", 2)?));
+        assert_eq!(float.workspace, Some(workspace(2)?));
+
+        let closed = enricher.resolve(Event::WindowClosed(Address::new("1")));
+        assert_eq!(closed.client.map(|client| client.pid), Some(4242));
+        assert!(enricher.client(&Address::new("1")).is_none());
+        let closed = enricher.resolve(Event::WindowClosed(Address::new("1")));
+        assert_eq!(closed.client, None);
+
+        let deleted = enricher.resolve(Event::WorkspaceDeleted(WorkspaceEventData {
+            name: crate::shared::WorkspaceType::Regular("3".to_string()),
+            id: 3,
+        }));
+        assert_eq!(deleted.workspace, Some(workspace(3)?));
+        assert!(enricher.workspace(3).is_none());
+
+        // but are dropped once a second fetch misses them too
+        enricher.clients.update([]);
+        assert!(enricher.client(&Address::new("2")).is_some());
+        enricher.clients.update([]);
+        assert!(enricher.client(&Address::new("2")).is_none());
+
+        let reload = enricher.resolve(Event::ConfigReloaded);
+        assert_eq!((reload.client, reload.workspace), (None, None));
+        Ok(())
+    }
+}
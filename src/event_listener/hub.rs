@@ -0,0 +1,586 @@
+use super::*;
+use crate::default_instance;
+use crate::error::{HyprError, hypr_err};
+use crate::instance::Instance;
+use futures_lite::{Stream, StreamExt};
+use std::collections::VecDeque;
+use std::future::poll_fn;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, Weak};
+use std::task::{Context, Poll, Waker};
+
+/// The default amount of events a [Subscription] buffers
+pub const DEFAULT_CAPACITY: usize = 256;
+
+/// What happens when an event arrives for a [Subscription] with a full buffer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LagPolicy {
+    /// Drops the oldest buffered event to make room
+    DropOldest,
+    /// Waits for the subscriber to catch up, which holds back every other subscriber too
+    Block,
+    /// Drops the new event, and yields a [HyprError::Lagged] with the amount of dropped events
+    /// once the subscriber catches up
+    #[default]
+    Lagged,
+}
+
+/// Reads events from a single socket connection, parses them once and hands them out to any
+/// amount of [Subscription]s
+///
+/// The hub does nothing until [EventHub::run] is polled, usually on a task of its own
+///
+/// ```rust, no_run
+/// use hyprland::event_listener::{EventHub, EventKind, LagPolicy, WindowOpenEvent};
+/// use futures_lite::StreamExt;
+///
+/// #[tokio::main(flavor = "current_thread")]
+/// async fn main() -> hyprland::Result<()> {
+///     let hub = EventHub::new();
+///     let mut bar = hub
+///         .subscribe_with(64, LagPolicy::DropOldest)
+///         .filter_kinds(&[EventKind::WorkspaceChanged, EventKind::ActiveWindowChanged]);
+///     let mut rules = hub.subscribe_with(256, LagPolicy::Block).only::<WindowOpenEvent>();
+///     tokio::spawn(async move {
+///         while let Some(window) = rules.next().await {
+///             println!("{} opened", window?.window_class);
+///         }
+///         hyprland::Result::Ok(())
+///     });
+///     tokio::spawn(async move {
+///         while let Some(event) = bar.next().await {
+///             println!("{:?}", event?);
+///         }
+///         hyprland::Result::Ok(())
+///     });
+///     hub.run().await
+/// }
+/// ```
+#[derive(Clone)]
+pub struct EventHub {
+    shared: Arc<HubShared>,
+}
+
+struct HubShared {
+    instance: Option<Instance>,
+    running: AtomicBool,
+    shutdown: ShutdownHandle,
+    subscribers: Mutex<Vec<Weak<SubscriberState>>>,
+}
+
+type EventFilter = Arc<dyn Fn(&Event) -> bool + Send + Sync>;
+
+struct SubscriberState {
+    capacity: usize,
+    policy: LagPolicy,
+    hub: Weak<HubShared>,
+    filter: Filter,
+    queue: Mutex<Queue>,
+}
+
+#[derive(Clone, Default)]
+struct Filter {
+    kinds: KindSet,
+    predicate: Option<EventFilter>,
+}
+
+enum Slot {
+    Event(Event),
+    Lagged(u64),
+}
+
+#[derive(Default)]
+struct Queue {
+    slots: VecDeque<Slot>,
+    /// The amount of [Slot::Event]s, as lag markers don't take up space
+    events: usize,
+    closed: bool,
+    dropped: bool,
+    error: Option<HyprError>,
+    reader: Option<Waker>,
+    writer: Option<Waker>,
+    /// The subscriber that took over once the filters changed, see [Subscription::refilter]
+    successor: Option<Weak<SubscriberState>>,
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+impl Default for EventHub {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EventHub {
+    /// Creates a hub for the default instance
+    pub fn new() -> Self {
+        Self::with_instance(None)
+    }
+
+    /// Creates a hub for an instance
+    pub fn instance_new(instance: Instance) -> Self {
+        Self::with_instance(Some(instance))
+    }
+
+    fn with_instance(instance: Option<Instance>) -> Self {
+        Self {
+            shared: Arc::new(HubShared {
+                instance,
+                running: AtomicBool::new(false),
+                shutdown: ShutdownHandle::new(),
+                subscribers: Mutex::new(vec![]),
+            }),
+        }
+    }
+
+    /// Subscribes to the events, buffering up to [DEFAULT_CAPACITY] of them with the default
+    /// [LagPolicy]
+    ///
+    /// A subscription only gets the events received after it was created
+    pub fn subscribe(&self) -> Subscription {
+        self.subscribe_with(DEFAULT_CAPACITY, LagPolicy::default())
+    }
+
+    /// Subscribes to the events, buffering up to `capacity` of them
+    ///
+    /// A subscription only gets the events received after it was created
+    pub fn subscribe_with(&self, capacity: usize, policy: LagPolicy) -> Subscription {
+        self.shared.subscribe(capacity, policy, Filter::default())
+    }
+
+    /// Returns a handle that stops [EventHub::run] from another thread or task
+    pub fn shutdown_handle(&self) -> ShutdownHandle {
+        self.shared.shutdown.clone()
+    }
+
+    /// Reads the events and hands them out until the socket closes or the hub is shut down
+    ///
+    /// Subscriptions end once this returns, an error is yielded to each of them first
+    pub async fn run(&self) -> crate::Result<()> {
+        if self.shared.running.swap(true, Ordering::SeqCst) {
+            hypr_err!("the event hub is already running");
+        }
+        let result = self.read_events().await;
+        self.shared.close(result.as_ref().err());
+        self.shared.shutdown.reset();
+        self.shared.running.store(false, Ordering::SeqCst);
+        result
+    }
+
+    async fn read_events(&self) -> crate::Result<()> {
+        let instance = match &self.shared.instance {
+            Some(instance) => instance,
+            None => default_instance()?,
+        };
+        let shutdown = &self.shared.shutdown;
+        let mut stream = instance.get_event_stream_async().await?;
+        let mut active_windows = vec![];
+        let mut lines = EventLineBuffer::new();
+        loop {
+            let mut buffer = [0; 4096];
+            let Some(bytes_read) = read_async(&mut stream, &mut buffer, shutdown, None).await?
+            else {
+                break;
+            };
+            for event in parse_lines(lines.push(&buffer[..bytes_read]))? {
+                for event in event_primer_noexec(event, &mut active_windows)? {
+                    self.publish(event).await;
+                }
+            }
+            if bytes_read == 0 {
+                // If no bytes were read, we can assume the stream is closed
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    /// Hands an event out to the subscribers that want it
+    pub(crate) async fn publish(&self, event: Event) {
+        let subscribers: Vec<_> = {
+            let mut subscribers = lock(&self.shared.subscribers);
+            subscribers.retain(|subscriber| subscriber.strong_count() > 0);
+            subscribers.iter().filter_map(Weak::upgrade).collect()
+        };
+        for subscriber in subscribers {
+            if !subscriber.wants(&event) {
+                continue;
+            }
+            if subscriber.policy == LagPolicy::Block {
+                let stop = async {
+                    self.shared.shutdown.wait().await;
+                    false
+                };
+                let space = async {
+                    subscriber.space().await;
+                    true
+                };
+                if !futures_lite::future::or(stop, space).await {
+                    return;
+                }
+            }
+            subscriber.push(event.clone());
+        }
+    }
+}
+
+impl HubShared {
+    fn subscribe(
+        self: &Arc<Self>,
+        capacity: usize,
+        policy: LagPolicy,
+        filter: Filter,
+    ) -> Subscription {
+        let state = Arc::new(SubscriberState {
+            capacity: capacity.max(1),
+            policy,
+            hub: Arc::downgrade(self),
+            filter,
+            queue: Mutex::new(Queue::default()),
+        });
+        lock(&self.subscribers).push(Arc::downgrade(&state));
+        Subscription { state }
+    }
+
+    /// Ends every subscription, giving each of them a copy of the error
+    fn close(&self, error: Option<&HyprError>) {
+        for subscriber in lock(&self.subscribers).drain(..) {
+            if let Some(subscriber) = subscriber.upgrade() {
+                let error = error.map(|error| match error.try_as_cloned() {
+                    Ok(error) => error,
+                    Err(error) => HyprError::Other(error.to_string()),
+                });
+                subscriber.close(error);
+            }
+        }
+    }
+}
+
+impl Drop for HubShared {
+    fn drop(&mut self) {
+        self.close(None);
+    }
+}
+
+impl SubscriberState {
+    fn wants(&self, event: &Event) -> bool {
+        self.filter.kinds.contains(event.kind())
+            && self.filter.predicate.as_ref().is_none_or(|f| f(event))
+    }
+
+    /// Waits until the buffer has room or the subscription is dropped
+    async fn space(&self) {
+        poll_fn(|cx| {
+            let mut queue = lock(&self.queue);
+            if queue.events < self.capacity || queue.dropped || queue.closed {
+                Poll::Ready(())
+            } else {
+                queue.writer = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        })
+        .await
+    }
+
+    fn push(&self, event: Event) {
+        let mut queue = lock(&self.queue);
+        if let Some(successor) = queue.successor.as_ref().and_then(Weak::upgrade) {
+            drop(queue);
+            if successor.wants(&event) {
+                successor.push(event);
+            }
+            return;
+        }
+        if queue.dropped || queue.closed {
+            return;
+        }
+        if queue.events >= self.capacity {
+            match self.policy {
+                LagPolicy::DropOldest => {
+                    if let Some(index) = queue
+                        .slots
+                        .iter()
+                        .position(|slot| matches!(slot, Slot::Event(_)))
+                    {
+                        queue.slots.remove(index);
+                        queue.events -= 1;
+                    }
+                }
+                LagPolicy::Lagged => {
+                    match queue.slots.back_mut() {
+                        Some(Slot::Lagged(n)) => *n += 1,
+                        _ => queue.slots.push_back(Slot::Lagged(1)),
+                    }
+                    return;
+                }
+                // the hub waited for space before pushing
+                LagPolicy::Block => {}
+            }
+        }
+        queue.slots.push_back(Slot::Event(event));
+        queue.events += 1;
+        if let Some(reader) = queue.reader.take() {
+            reader.wake();
+        }
+    }
+
+    fn close(&self, error: Option<HyprError>) {
+        let mut queue = lock(&self.queue);
+        queue.closed = true;
+        queue.error = error;
+        if let Some(reader) = queue.reader.take() {
+            reader.wake();
+        }
+    }
+}
+
+/// A stream of the events handed out by an [EventHub]
+///
+/// Cloning a subscription subscribes again with the same buffer, lag policy and filters, the
+/// clone gets the events received after it was created
+#[must_use = "streams nothing unless polled"]
+pub struct Subscription {
+    state: Arc<SubscriberState>,
+}
+
+impl Subscription {
+    /// Only yields events of the given kinds
+    ///
+    /// The other events are never buffered, so they can't make the subscriber lag.
+    /// Without a filter every event but the legacy ones is yielded, see [EventKind::is_legacy]
+    pub fn filter_kinds(self, kinds: &[EventKind]) -> Self {
+        let filter = Filter {
+            kinds: KindSet::from_kinds(kinds),
+            ..self.state.filter.clone()
+        };
+        self.refilter(filter)
+    }
+
+    /// Only yields the events the predicate returns `true` for
+    ///
+    /// The other events are never buffered, so they can't make the subscriber lag
+    pub fn filter(self, predicate: impl Fn(&Event) -> bool + Send + Sync + 'static) -> Self {
+        let filter = Filter {
+            predicate: Some(Arc::new(predicate)),
+            ..self.state.filter.clone()
+        };
+        self.refilter(filter)
+    }
+
+    /// Only yields the data of the events carrying `T`
+    pub fn only<T: EventData>(self) -> impl Stream<Item = crate::Result<T>> + Send {
+        self.filter_kinds(&[T::KIND])
+            .filter_map(|event| match event {
+                Ok(event) => T::from_event(event).map(Ok),
+                Err(e) => Some(Err(e)),
+            })
+    }
+}
+
+impl Subscription {
+    /// Swaps in a subscriber with the new filter, which keeps the buffered events passing it
+    ///
+    /// The old subscriber forwards the events the hub is handing out during the swap
+    fn refilter(self, filter: Filter) -> Self {
+        let state = Arc::new(SubscriberState {
+            capacity: self.state.capacity,
+            policy: self.state.policy,
+            hub: self.state.hub.clone(),
+            filter,
+            queue: Mutex::new(Queue::default()),
+        });
+        let hub = self.state.hub.upgrade();
+        // the hub locks the subscribers before their queues, so the swap does too
+        let mut subscribers = hub.as_ref().map(|hub| lock(&hub.subscribers));
+        if let Some(subscribers) = subscribers.as_mut() {
+            let old = Arc::downgrade(&self.state);
+            for subscriber in subscribers.iter_mut() {
+                if subscriber.ptr_eq(&old) {
+                    *subscriber = Arc::downgrade(&state);
+                }
+            }
+        }
+        let mut old = lock(&self.state.queue);
+        let mut queue = lock(&state.queue);
+        for slot in old.slots.drain(..) {
+            match slot {
+                Slot::Event(event) if !state.wants(&event) => {}
+                Slot::Event(event) => {
+                    queue.slots.push_back(Slot::Event(event));
+                    queue.events += 1;
+                }
+                lagged => queue.slots.push_back(lagged),
+            }
+        }
+        old.events = 0;
+        old.successor = Some(Arc::downgrade(&state));
+        queue.closed = old.closed;
+        queue.error = old.error.take();
+        drop((queue, old, subscribers));
+        Subscription { state }
+    }
+}
+
+impl Clone for Subscription {
+    fn clone(&self) -> Self {
+        let filter = self.state.filter.clone();
+        match self.state.hub.upgrade() {
+            Some(hub) => hub.subscribe(self.state.capacity, self.state.policy, filter),
+            // the hub is gone, so the clone ends right away
+            None => {
+                let state = SubscriberState {
+                    capacity: self.state.capacity,
+                    policy: self.state.policy,
+                    hub: Weak::new(),
+                    filter,
+                    queue: Mutex::new(Queue::default()),
+                };
+                state.close(None);
+                Subscription {
+                    state: Arc::new(state),
+                }
+            }
+        }
+    }
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        let mut queue = lock(&self.state.queue);
+        queue.dropped = true;
+        // a blocked hub waiting on this subscriber can move on
+        if let Some(writer) = queue.writer.take() {
+            writer.wake();
+        }
+    }
+}
+
+impl Stream for Subscription {
+    type Item = crate::Result<Event>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut queue = lock(&self.state.queue);
+        match queue.slots.pop_front() {
+            Some(Slot::Event(event)) => {
+                queue.events -= 1;
+                if let Some(writer) = queue.writer.take() {
+                    writer.wake();
+                }
+                Poll::Ready(Some(Ok(event)))
+            }
+            Some(Slot::Lagged(n)) => Poll::Ready(Some(Err(HyprError::Lagged(n)))),
+            None if queue.closed => Poll::Ready(queue.error.take().map(Err)),
+            None => {
+                queue.reader = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "tokio"))]
+mod tests {
+    use super::*;
+    use futures_lite::future::poll_once;
+
+    fn submap(name: &str) -> Event {
+        Event::SubMapChanged(name.to_string())
+    }
+
+    async fn drain(subscription: &mut Subscription) -> Vec<String> {
+        let mut items = vec![];
+        while let Some(Some(item)) = poll_once(subscription.next()).await {
+            items.push(match item {
+                Ok(event) => format!("{event:?}"),
+                Err(e) => e.to_string(),
+            });
+        }
+        items
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn test_hub_lag_policies() {
+        let hub = EventHub::new();
+        let mut oldest = hub.subscribe_with(2, LagPolicy::DropOldest);
+        let mut lagged = hub.subscribe_with(2, LagPolicy::Lagged);
+        let mut reloads = hub.subscribe().filter_kinds(&[EventKind::ConfigReloaded]);
+        let mut named = hub
+            .subscribe()
+            .filter(|event| matches!(event, Event::SubMapChanged(name) if name == "c"));
+        for event in [submap("a"), submap("b"), Event::ConfigReloaded, submap("c")] {
+            hub.publish(event).await;
+        }
+        assert_eq!(
+            drain(&mut oldest).await,
+            [r#"ConfigReloaded"#, r#"SubMapChanged("c")"#]
+        );
+        assert_eq!(
+            drain(&mut lagged).await,
+            [
+                r#"SubMapChanged("a")"#,
+                r#"SubMapChanged("b")"#,
+                "The subscriber lagged behind and missed 2 event(s)"
+            ]
+        );
+        assert_eq!(drain(&mut reloads).await, ["ConfigReloaded"]);
+        assert_eq!(drain(&mut named).await, [r#"SubMapChanged("c")"#]);
+
+        // a clone only gets new events, with the same filters
+        let mut clone = named.clone();
+        hub.publish(submap("c")).await;
+        assert_eq!(drain(&mut clone).await, [r#"SubMapChanged("c")"#]);
+
+        // subscriptions end once the hub is gone
+        drop(hub);
+        assert_eq!(named.next().await.map(|e| e.is_ok()), Some(true));
+        assert!(named.next().await.is_none());
+        assert!(clone.next().await.is_none());
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn test_hub_block() {
+        let hub = EventHub::new();
+        let mut blocking = hub.subscribe_with(1, LagPolicy::Block);
+        hub.publish(submap("a")).await;
+        let mut publish = Box::pin(hub.publish(submap("b")));
+        assert!(poll_once(&mut publish).await.is_none());
+        assert_eq!(drain(&mut blocking).await, [r#"SubMapChanged("a")"#]);
+        publish.await;
+        assert_eq!(drain(&mut blocking).await, [r#"SubMapChanged("b")"#]);
+
+        // a dropped subscriber doesn't hold back the hub
+        hub.publish(submap("c")).await;
+        let mut publish = Box::pin(hub.publish(submap("d")));
+        assert!(poll_once(&mut publish).await.is_none());
+        drop(blocking);
+        publish.await;
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn test_hub_refilter() {
+        let hub = EventHub::new();
+        let subscription = hub.subscribe_with(1, LagPolicy::Block);
+        hub.publish(submap("a")).await;
+        let mut publish = Box::pin(hub.publish(Event::ConfigReloaded));
+        assert!(poll_once(&mut publish).await.is_none());
+
+        // the buffered events are filtered again and the pending one is handed to the new filter
+        let mut reloads = subscription.filter_kinds(&[EventKind::ConfigReloaded]);
+        publish.await;
+        hub.publish(submap("b")).await;
+        assert_eq!(drain(&mut reloads).await, ["ConfigReloaded"]);
+        assert_eq!(lock(&hub.shared.subscribers).len(), 1);
+    }
+
+    #[test]
+    fn test_hub_send() {
+        fn assert_send<T: Send>(_: &T) {}
+        let hub = EventHub::new();
+        assert_send(&hub.run());
+        assert_send(&hub.subscribe());
+        assert_send(&hub);
+    }
+}
@@ -1,6 +1,9 @@
 use super::*;
 use crate::default_instance;
 use crate::instance::Instance;
+use std::cell::{RefCell, RefMut};
+use std::rc::{Rc, Weak};
+use std::time::Instant;
 
 /// This struct is used for adding event handlers and executing them on events
 /// # The Event Listener
@@ -20,8 +23,59 @@ use crate::instance::Instance;
 ///     Ok(())
 /// }
 /// ```
+///
+/// ## Handlers
+///
+/// Adding a handler returns a [HandlerId] that can remove it again. Clones of the listener share
+/// its handlers, so a clone moved into a handler can add or remove handlers while the listener
+/// runs. Changes made while an event is handled apply from the next event
+///
+/// Handlers can return a [crate::Result]. Errors are handled by the [ErrorPolicy] set with
+/// [EventListener::set_error_policy], and [Middleware] added with [EventListener::add_middleware] runs
+/// around the handlers of every event
+///
+/// ```rust, no_run
+/// # use hyprland::event_listener::EventListener;
+/// let mut listener = EventListener::new();
+/// let handle = listener.clone();
+/// let id = listener.add_window_opened_handler(|data| println!("{data:#?}"));
+/// listener.add_workspace_changed_handler_once(move |_| {
+///     handle.remove_handler(id);
+/// });
+/// listener.start_listener();
+/// ```
+#[derive(Clone)]
 pub struct EventListener {
-    pub(crate) events: Events,
+    pub(crate) events: Rc<RefCell<Events>>,
+    shutdown: ShutdownHandle,
+}
+
+/// Removes a handler from an [EventListener] when dropped, see [EventListener::guard]
+#[must_use = "the handler is removed when the guard is dropped"]
+pub struct HandlerGuard {
+    id: HandlerId,
+    events: Weak<RefCell<Events>>,
+}
+
+impl HandlerGuard {
+    /// Returns the id of the handler
+    pub fn id(&self) -> HandlerId {
+        self.id
+    }
+
+    /// Keeps the handler, dropping the guard without removing it
+    pub fn disarm(mut self) -> HandlerId {
+        self.events = Weak::new();
+        self.id
+    }
+}
+
+impl Drop for HandlerGuard {
+    fn drop(&mut self) {
+        if let Some(events) = self.events.upgrade() {
+            events.borrow_mut().remove(self.id);
+        }
+    }
 }
 
 impl Default for EventListener {
@@ -39,7 +93,49 @@ impl EventListener {
     /// ```
     pub fn new() -> EventListener {
         EventListener {
-            events: create_events(),
+            events: Rc::new(RefCell::new(create_events())),
+            shutdown: ShutdownHandle::new(),
+        }
+    }
+
+    /// Returns a handle that stops the listener from another thread or task
+    pub fn shutdown_handle(&self) -> ShutdownHandle {
+        self.shutdown.clone()
+    }
+
+    pub(crate) fn lock_events(&self) -> RefMut<'_, Events> {
+        self.events.borrow_mut()
+    }
+
+    /// Removes a handler, returning if it was found
+    pub fn remove_handler(&self, id: HandlerId) -> bool {
+        self.lock_events().remove(id)
+    }
+
+    /// Adds middleware running around the handlers of every event, in the order it was added
+    pub fn add_middleware(&self, middleware: impl Middleware + 'static) {
+        self.lock_events().middleware.push(Rc::new(middleware));
+    }
+
+    /// Sets what happens when a handler returns an error
+    ///
+    /// By default ([ErrorPolicy::Log]) the errors are printed to stderr
+    pub fn set_error_policy(&self, policy: ErrorPolicy) {
+        self.lock_events().error_policy = policy;
+    }
+
+    /// Returns a guard that removes a handler when dropped
+    ///
+    /// ```rust, no_run
+    /// # use hyprland::event_listener::EventListener;
+    /// let listener = EventListener::new();
+    /// let guard = listener.guard(listener.add_window_opened_handler(|data| println!("{data:#?}")));
+    /// drop(guard); // the handler is removed
+    /// ```
+    pub fn guard(&self, id: HandlerId) -> HandlerGuard {
+        HandlerGuard {
+            id,
+            events: Rc::downgrade(&self.events),
         }
     }
 
@@ -83,27 +179,37 @@ impl EventListener {
         &mut self,
         instance: &Instance,
     ) -> crate::Result<()> {
-        use crate::async_import::*;
-        let mut stream = instance.get_event_stream_async().await?;
-
-        let mut active_windows = vec![];
-        loop {
-            let mut buffer = [0; 4096];
-            let bytes_read = stream.read(&mut buffer).await?;
-            if bytes_read == 0 {
-                // If no bytes were read, we can assume the stream is closed
-                break;
-            }
-            let buf = &buffer[..bytes_read];
-            let string = String::from_utf8_lossy(buf);
-            let parsed: Vec<Event> = event_parser(&string)?;
-            for event in parsed {
-                self.event_primer(event, &mut active_windows)?;
-            }
-        }
+        self.instance_run_until_async(instance, None).await?;
         Ok(())
     }
 
+    /// Runs the event listener until the deadline passes, it is shut down or the socket closes,
+    /// returning the fully read events it didn't handle, see [ShutdownHandle] (async)
+    ///
+    /// Pass [None] to run without a deadline
+    #[cfg(any(feature = "async-lite", feature = "tokio"))]
+    pub async fn run_until_async(
+        &mut self,
+        deadline: impl Into<Option<Instant>>,
+    ) -> crate::Result<Vec<Event>> {
+        self.instance_run_until_async(default_instance()?, deadline)
+            .await
+    }
+
+    /// Runs the event listener until the deadline passes, it is shut down or the socket closes,
+    /// returning the fully read events it didn't handle, see [ShutdownHandle] (async)
+    ///
+    /// Pass [None] to run without a deadline
+    #[cfg(any(feature = "async-lite", feature = "tokio"))]
+    pub async fn instance_run_until_async(
+        &mut self,
+        instance: &Instance,
+        deadline: impl Into<Option<Instant>>,
+    ) -> crate::Result<Vec<Event>> {
+        let shutdown = self.shutdown.clone();
+        run_async(self, instance, &shutdown, deadline.into()).await
+    }
+
     /// This method starts the event listener (blocking)
     ///
     /// This should be ran after all of your handlers are defined
@@ -136,25 +242,216 @@ impl EventListener {
     /// }
     /// ```
     pub fn instance_start_listener(&mut self, instance: &Instance) -> crate::Result<()> {
-        // use io::prelude::*;
-        use std::io::Read;
-        let mut stream = instance.get_event_stream()?;
+        self.instance_run_until(instance, None)?;
+        Ok(())
+    }
 
+    /// Runs the event listener until the deadline passes, it is shut down or the socket closes,
+    /// returning the fully read events it didn't handle, see [ShutdownHandle] (blocking)
+    ///
+    /// Partly read lines and half of an active window change are dropped.
+    /// Pass [None] to run without a deadline
+    /// ```rust, no_run
+    /// # use hyprland::event_listener;
+    /// use std::time::{Duration, Instant};
+    /// fn function() -> hyprland::Result<()> {
+    ///     let mut listener = event_listener::EventListener::new();
+    ///     listener.add_workspace_changed_handler(|data| println!("{:#?}", data));
+    ///     listener.run_until(Instant::now() + Duration::from_secs(5))?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn run_until(&mut self, deadline: impl Into<Option<Instant>>) -> crate::Result<Vec<Event>> {
+        self.instance_run_until(default_instance()?, deadline)
+    }
+
+    /// Runs the event listener until the deadline passes, it is shut down or the socket closes,
+    /// returning the fully read events it didn't handle, see [ShutdownHandle] (blocking)
+    ///
+    /// Pass [None] to run without a deadline
+    pub fn instance_run_until(
+        &mut self,
+        instance: &Instance,
+        deadline: impl Into<Option<Instant>>,
+    ) -> crate::Result<Vec<Event>> {
+        let shutdown = self.shutdown.clone();
+        run_blocking(self, instance, &shutdown, deadline.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    fn emit(listener: &mut EventListener, line: &str) {
         let mut active_windows = vec![];
-        loop {
-            let mut buffer = [0; 4096];
-            let bytes_read = stream.read(&mut buffer)?;
-            if bytes_read == 0 {
-                // If no bytes were read, we can assume the stream is closed
-                break;
-            }
-            let buf = &buffer[..bytes_read];
-            let string = String::from_utf8_lossy(buf);
-            let parsed: Vec<Event> = event_parser(&string)?;
-            for event in parsed {
-                self.event_primer(event, &mut active_windows)?;
+        let events = match event_parser(line) {
+            Ok(events) => events,
+            Err(e) => panic!("Error occured: {e}"), // Note to greppers: this is in a test!
+        };
+        for event in events {
+            if let Err(e) = listener.event_primer(event, &mut active_windows) {
+                panic!("Error occured: {e}");
             }
         }
-        Ok(())
+    }
+
+    #[test]
+    fn test_handlers() {
+        let mut listener = EventListener::new();
+        let calls = Rc::new(Cell::new(0));
+        let once_calls = Rc::new(Cell::new(0));
+
+        let counter = calls.clone();
+        let id = listener.add_config_reloaded_handler(move || counter.set(counter.get() + 1));
+        let counter = once_calls.clone();
+        listener.add_config_reloaded_handler_once(move || counter.set(counter.get() + 1));
+
+        emit(&mut listener, "configreloaded>>\nconfigreloaded>>");
+        assert_eq!((calls.get(), once_calls.get()), (2, 1));
+
+        assert!(listener.remove_handler(id));
+        assert!(!listener.remove_handler(id));
+        emit(&mut listener, "configreloaded>>");
+        assert_eq!(calls.get(), 2);
+
+        // handlers can add and remove handlers while the listener runs
+        let handle = listener.clone();
+        let counter = calls.clone();
+        listener.add_sub_map_changed_handler_once(move |_| {
+            let counter = counter.clone();
+            handle.add_config_reloaded_handler(move || counter.set(counter.get() + 1));
+        });
+        emit(&mut listener, "submap>>resize\nconfigreloaded>>");
+        assert_eq!(calls.get(), 3);
+
+        let counter = calls.clone();
+        let guard = listener
+            .guard(listener.add_sub_map_changed_handler(move |_| counter.set(counter.get() + 10)));
+        emit(&mut listener, "submap>>resize");
+        drop(guard);
+        emit(&mut listener, "submap>>resize");
+        assert_eq!(calls.get(), 13);
+    }
+
+    #[test]
+    fn test_fallible_handlers() {
+        use crate::error::HyprError;
+        use std::sync::{Arc, Mutex};
+
+        let mut listener = EventListener::new();
+        let seen = Rc::new(RefCell::new(vec![]));
+        let errors = Arc::new(Mutex::new(vec![]));
+
+        let log = seen.clone();
+        listener.add_middleware(move |event: Event| {
+            log.borrow_mut().push(format!("before {:?}", event.kind()));
+            match event {
+                Event::LayerOpened(name) if name == "hidden" => None,
+                event => Some(event),
+            }
+        });
+        listener.add_sub_map_changed_handler(|name| -> crate::Result<()> {
+            Err(HyprError::Other(format!("no submap {name}")))
+        });
+        let log = seen.clone();
+        listener.add_sub_map_changed_handler(move |name| log.borrow_mut().push(name));
+        let log = seen.clone();
+        listener.add_layer_opened_handler(move |name| log.borrow_mut().push(name));
+
+        let sink = errors.clone();
+        listener.set_error_policy(ErrorPolicy::callback(move |kind, error| {
+            if let Ok(mut errors) = sink.lock() {
+                errors.push(format!("{kind:?}: {error}"));
+            }
+        }));
+        emit(
+            &mut listener,
+            "submap>>resize\nopenlayer>>hidden\nopenlayer>>bar",
+        );
+        assert_eq!(
+            *seen.borrow(),
+            [
+                "before SubMapChanged",
+                "resize",
+                "before LayerOpened",
+                "before LayerOpened",
+                "bar"
+            ]
+        );
+        assert_eq!(
+            *errors.lock().unwrap_or_else(|e| e.into_inner()),
+            ["SubMapChanged: no submap resize"]
+        );
+
+        // the error stops the listener before the handlers after the failing one
+        listener.set_error_policy(ErrorPolicy::Stop);
+        seen.borrow_mut().clear();
+        let mut active_windows = vec![];
+        let result = listener.event_primer(
+            Event::SubMapChanged("reset".to_string()),
+            &mut active_windows,
+        );
+        assert!(result.is_err());
+        assert_eq!(*seen.borrow(), ["before SubMapChanged"]);
+    }
+
+    #[test]
+    fn test_once_handlers_after_error() {
+        use crate::error::HyprError;
+
+        let mut listener = EventListener::new();
+        listener.set_error_policy(ErrorPolicy::Stop);
+        let fail = Rc::new(Cell::new(true));
+        let failing = fail.clone();
+        listener.add_config_reloaded_handler(move || -> crate::Result<()> {
+            if failing.get() {
+                Err(HyprError::Other("failed".to_string()))
+            } else {
+                Ok(())
+            }
+        });
+        let ran = Rc::new(RefCell::new(vec![]));
+        let log = ran.clone();
+        // once handlers can move what they capture
+        let message = "ran once".to_string();
+        listener.add_config_reloaded_handler_once(move || log.borrow_mut().push(message));
+
+        let mut active_windows = vec![];
+        let result = listener.event_primer(Event::ConfigReloaded, &mut active_windows);
+        assert!(result.is_err());
+        // the error stopped the event before the once handler ran, so it is kept
+        assert!(ran.borrow().is_empty());
+        fail.set(false);
+        emit(&mut listener, "configreloaded>>\nconfigreloaded>>");
+        assert_eq!(*ran.borrow(), ["ran once"]);
+    }
+
+    #[test]
+    fn test_legacy_events() {
+        let mut listener = EventListener::new();
+        let seen = Rc::new(RefCell::new(vec![]));
+
+        let log = seen.clone();
+        listener.add_middleware(move |event: Event| {
+            log.borrow_mut().push(event.kind());
+            Some(event)
+        });
+        let titles = "windowtitle>>5d3a1f80\nwindowtitlev2>>5d3a1f80,vim";
+        emit(&mut listener, titles);
+        assert_eq!(*seen.borrow(), [EventKind::WindowTitleChanged]);
+
+        // a handler asks for the legacy event
+        seen.borrow_mut().clear();
+        listener.add_window_title_changed_v1_handler(|_| ());
+        emit(&mut listener, titles);
+        assert_eq!(
+            *seen.borrow(),
+            [
+                EventKind::WindowTitleChangedV1,
+                EventKind::WindowTitleChanged
+            ]
+        );
     }
 }
@@ -1,21 +1,105 @@
 macro_rules! events {
-    ($($name:ty => $data:ty,$descr1:literal,$descr2:literal => $id:ident);*) => {
+    ($($name:ident => $data:ty,$descr1:literal,$descr2:literal => $id:ident);*) => {
+        /// The kind of an [Event], without its data
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum EventKind {
+            $(
+                #[doc = concat!("The kind of [Event::", stringify!($name), "]")]
+                $name
+            ),*
+        }
+
+        impl Event {
+            /// Returns the kind of the event
+            pub fn kind(&self) -> EventKind {
+                match self {
+                    // the intermediary events are combined into ActiveWindowChanged
+                    Event::ActiveWindowChangedV1(_) | Event::ActiveWindowChangedV2(_) => {
+                        EventKind::ActiveWindowChanged
+                    }
+                    $(Event::$name { .. } => EventKind::$name),*
+                }
+            }
+        }
+
         paste! {
+            #[allow(clippy::type_complexity)]
             pub(crate) struct Events {
+                pub(crate) middleware: Vec<std::rc::Rc<dyn Middleware>>,
+                pub(crate) error_policy: ErrorPolicy,
                 $(
-                    pub(crate) [<$name:snake _events>]: type_if! {(),$data,Vec<EmptyClosure>, Closures<$data>}
+                    pub(crate) [<$name:snake _events>]: type_if! {(),$data,Handlers<EmptyClosure>, Closures<$data>}
                 ),*
             }
 
+            impl Events {
+                pub(crate) fn remove(&mut self, id: HandlerId) -> bool {
+                    false $(|| remove_handler(&mut self.[<$name:snake _events>], id))*
+                }
+
+                pub(crate) fn has_handlers(&self, kind: EventKind) -> bool {
+                    match kind {
+                        $(EventKind::$name => !self.[<$name:snake _events>].is_empty()),*
+                    }
+                }
+            }
+
             #[cfg(any(feature = "async-lite", feature = "tokio"))]
             #[allow(clippy::type_complexity)]
             pub(crate) struct AsyncEvents {
+                pub(crate) middleware: Vec<std::sync::Arc<dyn Middleware + Send + Sync>>,
+                pub(crate) error_policy: ErrorPolicy,
+                $(
+                    pub(crate) [<$name:snake _events>]: type_if! {(),$data,Handlers<EmptyAsyncClosure>, AsyncClosures<$data>}
+                ),*
+            }
+
+            #[cfg(any(feature = "async-lite", feature = "tokio"))]
+            impl AsyncEvents {
+                pub(crate) fn remove(&mut self, id: HandlerId) -> bool {
+                    false $(|| remove_handler(&mut self.[<$name:snake _events>], id))*
+                }
+
+                pub(crate) fn has_handlers(&self, kind: EventKind) -> bool {
+                    match kind {
+                        $(EventKind::$name => !self.[<$name:snake _events>].is_empty()),*
+                    }
+                }
+            }
+
+            #[allow(clippy::type_complexity)]
+            pub(crate) struct SendEvents {
+                pub(crate) middleware: Vec<std::sync::Arc<dyn Middleware + Send + Sync>>,
+                pub(crate) error_policy: ErrorPolicy,
                 $(
-                    pub(crate) [<$name:snake _events>]: type_if! {(),$data,Vec<EmptyAsyncClosure>, AsyncClosures<$data>}
+                    pub(crate) [<$name:snake _events>]: type_if! {(),$data,Handlers<EmptySendClosure>, SendClosures<$data>}
                 ),*
             }
+
+            impl SendEvents {
+                pub(crate) fn remove(&mut self, id: HandlerId) -> bool {
+                    false $(|| remove_handler(&mut self.[<$name:snake _events>], id))*
+                }
+
+                pub(crate) fn has_handlers(&self, kind: EventKind) -> bool {
+                    match kind {
+                        $(EventKind::$name => !self.[<$name:snake _events>].is_empty()),*
+                    }
+                }
+            }
+
             pub(crate) fn create_events() -> Events {
                 Events {
+                    middleware: vec![],
+                    error_policy: ErrorPolicy::default(),
+                    $([<$name:snake _events>]: vec![]),*
+                }
+            }
+
+            pub(crate) fn create_events_send() -> SendEvents {
+                SendEvents {
+                    middleware: vec![],
+                    error_policy: ErrorPolicy::default(),
                     $([<$name:snake _events>]: vec![]),*
                 }
             }
@@ -23,6 +107,8 @@ macro_rules! events {
             #[cfg(any(feature = "async-lite", feature = "tokio"))]
             pub(crate) fn create_events_async() -> AsyncEvents {
                 AsyncEvents {
+                    middleware: vec![],
+                    error_policy: ErrorPolicy::default(),
                     $([<$name:snake _events>]: vec![]),*
                 }
             }
@@ -30,38 +116,41 @@ macro_rules! events {
             #[cfg(any(feature = "async-lite", feature = "tokio"))]
             impl HasAsyncExecutor for AsyncEventListener {
                 async fn event_executor_async(&mut self, event: Event) -> crate::Result<()> {
-                    use Event::*;
-                    match event {
-                        $(
-                            expr_if! {(),$data, $name, $name($id)} => expr_if! {
-                                (),
-                                $data,
-                                arm_async!([<$name:snake _events>], self),
-                                arm_async!($id, [<$name:snake _events>], self)
-                            },
-                        )*
-                        _ => ()
+                    let (middleware, policy) = {
+                        let events = self.lock_events();
+                        // legacy events only reach listeners with handlers for them
+                        if event.kind().is_legacy() && !events.has_handlers(event.kind()) {
+                            return Ok(());
+                        }
+                        (events.middleware.clone(), events.error_policy.clone())
+                    };
+                    let Some(event) = before_handlers(&middleware, &policy, event)? else {
+                        return Ok(());
+                    };
+                    let kind = event.kind();
+                    let start = std::time::Instant::now();
+                    let result: crate::Result<()> = async {
+                        use Event::*;
+                        match event {
+                            $(
+                                expr_if! {(),$data, $name, $name($id)} => expr_if! {
+                                    (),
+                                    $data,
+                                    arm_async!([<$name:snake _events>], self, policy, kind),
+                                    arm_async!($id, [<$name:snake _events>], self, policy, kind)
+                                },
+                            )*
+                            _ => ()
+                        }
+                        Ok(())
                     }
-                    Ok(())
-                }
-            }
-            impl HasExecutor for EventListener {
-                fn event_executor(&mut self, event: Event) -> crate::Result<()> {
-                    use Event::*;
-                    match event {
-                        $(
-                            expr_if! {(),$data, $name, $name($id)} => expr_if! {
-                                (),
-                                $data,
-                                arm!([<$name:snake _events>], self),
-                                arm!($id, [<$name:snake _events>], self)
-                            },
-                        )*
-                        _ => ()
-                    }
-                    Ok(())
+                    .await;
+                    after_handlers(&middleware, kind, start.elapsed(), &result);
+                    result
                 }
             }
+            blocking_executor!(SendEventListener; $($name => $data => $id);*);
+            blocking_executor!(EventListener; $($name => $data => $id);*);
         }
         $(
             paste!{
@@ -80,14 +169,33 @@ macro_rules! events {
     };
 }
 
+macro_rules! event_data {
+    ($($name:ident => $data:ty);*) => {
+        $(
+            impl EventData for $data {
+                const KIND: EventKind = EventKind::$name;
+
+                fn from_event(event: Event) -> Option<Self> {
+                    match event {
+                        Event::$name(data) => Some(data),
+                        _ => None,
+                    }
+                }
+            }
+        )*
+    };
+}
+
 macro_rules! add_listener {
     ($name:ident,$f:ty,$c:literal,$c2:literal => $id:ident) => {
         add_listener_reg!($name,$f,$c,$c2 => $id);
+        add_send_listener!($name,$f,$c,$c2 => $id);
         #[cfg(any(feature = "async-lite", feature = "tokio"))]
         add_async_listener!($name,$f,$c,$c2 => $id);
     };
     ($name:ident,$c:literal,$c2:literal => $id:ident) => {
         add_listener_reg!($name,$c,$c2 => $id);
+        add_send_listener!($name,$c,$c2 => $id);
         #[cfg(any(feature = "async-lite", feature = "tokio"))]
         add_async_listener!($name,$c,$c2 => $id);
     };
@@ -96,33 +204,70 @@ macro_rules! add_listener {
 #[cfg(any(feature = "async-lite", feature = "tokio"))]
 macro_rules! add_async_listener {
     ($name:ident,$f:ty,$c:literal,$c2:expr => $id:ident) => {
-        add_async_listener_raw!($name,$name,impl Fn($f) -> VoidFuture + Send + Sync + 'static,$c,$c2 => $id);
+        add_async_listener_raw!($name,$name,$f,($f),async_handler,$c,$c2 => $id);
     };
     ($name:ident,$c:literal,$c2:expr => $id:ident) => {
-        add_async_listener_raw!($name,$name,impl Fn() -> VoidFuture + Send + Sync + 'static,$c,$c2 => $id);
+        add_async_listener_raw!($name,$name,(),(),empty_async_handler,$c,$c2 => $id);
     };
 }
 macro_rules! add_listener_reg {
     ($name:ident,$f:ty,$c:literal,$c2:expr => $id:ident) => {
-        add_listener_reg_raw!($name,$name,impl Fn($f) + 'static,$c,$c2 => $id);
+        add_listener_reg_raw!($name,$name,$f,($f),handler,$c,$c2 => $id);
+    };
+    ($name:ident,$c:literal,$c2:expr => $id:ident) => {
+        add_listener_reg_raw!($name,$name,(),(),empty_handler,$c,$c2 => $id);
+    };
+}
+
+macro_rules! add_send_listener {
+    ($name:ident,$f:ty,$c:literal,$c2:expr => $id:ident) => {
+        add_send_listener_raw!($name,$name,$f,($f),send_handler,$c,$c2 => $id);
     };
     ($name:ident,$c:literal,$c2:expr => $id:ident) => {
-        add_listener_reg_raw!($name,$name,impl Fn() + 'static,$c,$c2 => $id);
+        add_send_listener_raw!($name,$name,(),(),empty_send_handler,$c,$c2 => $id);
     };
 }
 
 macro_rules! add_listener_reg_raw {
-    ($name:ident,$list_name:ident,$f:ty,$c:literal,$c2:expr => $id:ident) => {
+    ($name:ident,$list_name:ident,$data:ty,($($arg:ty)?),$wrap:ident,$c:literal,$c2:expr => $id:ident) => {
         paste! {
             impl EventListener {
                 #[doc = concat!("This method adds an event which executes when", stringify!($c), r#"
 ```rust, no_run
 use hyprland::event_listener::EventListener;
 let mut listener = EventListener::new();
-listener.add_"#, stringify!($name), r#"_handler("#, handler_example_closure! { $f, $c2, $id }, r#");
+listener.add_"#, stringify!($name), r#"_handler("#, handler_example_closure! { $data, $c2, $id }, r#");
+listener.start_listener();"#)]
+                pub fn [<add_ $name _handler>]<R: HandlerResult>(&self, f: impl Fn($($arg)?) -> R + 'static) -> HandlerId {
+                    push_handler(&mut self.lock_events().[<$list_name _events>], $wrap(f), false)
+                }
+
+                #[doc = concat!("This method adds an event which executes once, the next time ", $c)]
+                pub fn [<add_ $name _handler_once>]<R: HandlerResult>(&self, f: impl FnOnce($($arg)?) -> R + 'static) -> HandlerId {
+                    push_handler(&mut self.lock_events().[<$list_name _events>], [<$wrap _once>](f), true)
+                }
+            }
+        }
+    };
+}
+
+macro_rules! add_send_listener_raw {
+    ($name:ident,$list_name:ident,$data:ty,($($arg:ty)?),$wrap:ident,$c:literal,$c2:expr => $id:ident) => {
+        paste! {
+            impl SendEventListener {
+                #[doc = concat!("This method adds an event which executes when ", $c, r#"
+```rust, no_run
+use hyprland::event_listener::SendEventListener;
+let mut listener = SendEventListener::new();
+listener.add_"#, stringify!($name), r#"_handler("#, handler_example_closure! { $data, $c2, $id }, r#");
 listener.start_listener();"#)]
-                pub fn [<add_ $name _handler>](&mut self, f: $f) {
-                    self.events.[<$list_name _events>].push(Box::new(f));
+                pub fn [<add_ $name _handler>]<R: HandlerResult>(&self, f: impl Fn($($arg)?) -> R + Send + Sync + 'static) -> HandlerId {
+                    push_handler(&mut self.lock_events().[<$list_name _events>], $wrap(f), false)
+                }
+
+                #[doc = concat!("This method adds an event which executes once, the next time ", $c)]
+                pub fn [<add_ $name _handler_once>]<R: HandlerResult>(&self, f: impl FnOnce($($arg)?) -> R + Send + 'static) -> HandlerId {
+                    push_handler(&mut self.lock_events().[<$list_name _events>], [<$wrap _once>](f), true)
                 }
             }
         }
@@ -131,17 +276,29 @@ listener.start_listener();"#)]
 
 #[cfg(any(feature = "async-lite", feature = "tokio"))]
 macro_rules! add_async_listener_raw {
-    ($name:ident,$list_name:ident,$f:ty,$c:literal,$c2:expr => $id:ident) => {
+    ($name:ident,$list_name:ident,$data:ty,($($arg:ty)?),$wrap:ident,$c:literal,$c2:expr => $id:ident) => {
         paste! {
             impl AsyncEventListener {
                 #[doc = concat!("This method adds an event which executes when ", $c, r#"
 ```rust, no_run
-use hyprland::event_listener::EventListener;
-let mut listener = EventListener::new();
-listener.add_"#, stringify!($name), r#"_handler("#, handler_example_async_closure! { $f, $c2, $id }, r#");
-listener.start_listener();"#)]
-                pub fn [<add_ $name _handler>](&mut self, f: $f) {
-                    self.events.[<$list_name _events>].push(Box::pin(f));
+use hyprland::event_listener::AsyncEventListener;
+let listener = AsyncEventListener::new();
+listener.add_"#, stringify!($name), r#"_handler("#, handler_example_async_closure! { $data, $c2, $id }, r#");"#)]
+                pub fn [<add_ $name _handler>]<F, Fut>(&self, f: F) -> HandlerId
+                where
+                    F: Fn($($arg)?) -> Fut + Send + Sync + 'static,
+                    Fut: std::future::Future<Output: HandlerResult> + Send + 'static,
+                {
+                    push_handler(&mut self.lock_events().[<$list_name _events>], $wrap(f), false)
+                }
+
+                #[doc = concat!("This method adds an event which executes once, the next time ", $c)]
+                pub fn [<add_ $name _handler_once>]<F, Fut>(&self, f: F) -> HandlerId
+                where
+                    F: FnOnce($($arg)?) -> Fut + Send + 'static,
+                    Fut: std::future::Future<Output: HandlerResult> + Send + 'static,
+                {
+                    push_handler(&mut self.lock_events().[<$list_name _events>], [<$wrap _once>](f), true)
                 }
             }
         }
@@ -151,7 +308,7 @@ listener.start_listener();"#)]
 macro_rules! handler_example_closure {
     ($f:ty, $c2:expr, $id:ident) => {
         type_if! {
-            impl Fn() + 'static ,
+            (),
             $f,
             concat!(
                 r#"|| println!(""#, $c2, r#"")"#,
@@ -167,45 +324,96 @@ macro_rules! handler_example_closure {
 macro_rules! handler_example_async_closure {
     ($f:ty, $c2:expr, $id:ident) => {
         type_if! {
-            impl Fn() -> VoidFuture + Send + Sync + 'static ,
+            (),
             $f,
             concat!(
-                r#"|| println!(""#, $c2, r#"")"#,
+                r#"async || println!(""#, $c2, r#"")"#,
             ),
             concat!(
-                r#"|"#, stringify!($id), r#"| println!(""#, $c2, ": {", stringify!($id), r#":#?}")"#,
+                r#"async |"#, stringify!($id), r#"| println!(""#, $c2, ": {", stringify!($id), r#":#?}")"#,
             )
         }
     }
 }
 
+/// Implements [HasExecutor] for a listener with blocking handlers, so every listener runs its
+/// handlers, middleware and error policy the same way
+macro_rules! blocking_executor {
+    ($listener:ty; $($name:ident => $data:ty => $id:ident);*) => {
+        paste! {
+            impl HasExecutor for $listener {
+                fn event_executor(&mut self, event: Event) -> crate::Result<()> {
+                    let (middleware, policy) = {
+                        let events = self.lock_events();
+                        // legacy events only reach listeners with handlers for them
+                        if event.kind().is_legacy() && !events.has_handlers(event.kind()) {
+                            return Ok(());
+                        }
+                        (events.middleware.clone(), events.error_policy.clone())
+                    };
+                    let Some(event) = before_handlers(&middleware, &policy, event)? else {
+                        return Ok(());
+                    };
+                    let kind = event.kind();
+                    let start = std::time::Instant::now();
+                    let result = (|| -> crate::Result<()> {
+                        use Event::*;
+                        match event {
+                            $(
+                                expr_if! {(),$data, $name, $name($id)} => expr_if! {
+                                    (),
+                                    $data,
+                                    arm!([<$name:snake _events>], self, policy, kind),
+                                    arm!($id, [<$name:snake _events>], self, policy, kind)
+                                },
+                            )*
+                            _ => ()
+                        }
+                        Ok(())
+                    })();
+                    after_handlers(&middleware, kind, start.elapsed(), &result);
+                    result
+                }
+            }
+        }
+    };
+}
+
 macro_rules! arm {
-    ($val:expr,$nam:ident,$se:ident) => {{
-        let events = &$se.events.$nam;
-        for item in events.iter() {
-            execute_closure(item, $val.clone());
+    ($val:expr,$nam:ident,$se:ident,$policy:ident,$kind:ident) => {{
+        let handlers = handlers_to_run(&$se.lock_events().$nam);
+        for handler in handlers.iter() {
+            let result = (handler.f)($val.clone());
+            handler_ran(&mut $se.lock_events().$nam, handler);
+            $policy.handle($kind, result)?;
         }
     }};
-    ($nam:ident,$se:ident) => {{
-        let events = &$se.events.$nam;
-        for item in events.iter() {
-            execute_empty_closure(item);
+    ($nam:ident,$se:ident,$policy:ident,$kind:ident) => {{
+        let handlers = handlers_to_run(&$se.lock_events().$nam);
+        for handler in handlers.iter() {
+            let result = (handler.f)();
+            handler_ran(&mut $se.lock_events().$nam, handler);
+            $policy.handle($kind, result)?;
         }
     }};
 }
 
 #[cfg(any(feature = "async-lite", feature = "tokio"))]
 macro_rules! arm_async {
-    ($val:expr,$nam:ident,$se:ident) => {{
-        let events = &$se.events.$nam;
-        for item in events.iter() {
-            execute_closure_async(item, $val.clone()).await;
+    ($val:expr,$nam:ident,$se:ident,$policy:ident,$kind:ident) => {{
+        let handlers = handlers_to_run(&$se.lock_events().$nam);
+        for handler in handlers.iter() {
+            let result = (handler.f)($val.clone()).await;
+            handler_ran(&mut $se.lock_events().$nam, handler);
+            $policy.handle($kind, result)?;
         }
     }};
-    ($nam:ident,$se:ident) => {{
-        let events = &$se.events.$nam;
-        for item in events.iter() {
-            execute_empty_closure_async(item).await;
+    ($nam:ident,$se:ident,$policy:ident,$kind:ident) => {{
+        let handlers = handlers_to_run(&$se.lock_events().$nam);
+        for handler in handlers.iter() {
+            let result = (handler.f)().await;
+            handler_ran(&mut $se.lock_events().$nam, handler);
+            $policy.handle($kind, result)?;
         }
     }};
 }
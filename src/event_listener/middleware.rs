@@ -0,0 +1,216 @@
+use super::{Event, EventKind};
+use crate::error::HyprError;
+use std::ops::Deref;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// The return value of a handler, letting handlers return `()` or a [crate::Result]
+///
+/// Errors returned by handlers are handled by the [ErrorPolicy] of the listener
+pub trait HandlerResult {
+    /// Converts the return value into a result
+    fn into_result(self) -> crate::Result<()>;
+}
+
+impl HandlerResult for () {
+    fn into_result(self) -> crate::Result<()> {
+        Ok(())
+    }
+}
+
+impl HandlerResult for crate::Result<()> {
+    fn into_result(self) -> crate::Result<()> {
+        self
+    }
+}
+
+/// What a listener does when a handler or [Middleware::inspect] returns an error
+///
+/// The default is [ErrorPolicy::Log], which prints every error to stderr. Use
+/// [ErrorPolicy::Ignore] or [ErrorPolicy::Callback] to keep a listener from writing to stderr
+#[derive(Clone, Default)]
+pub enum ErrorPolicy {
+    /// Prints the error to stderr with `eprintln!` and keeps going, the default
+    #[default]
+    Log,
+    /// Ignores the error
+    Ignore,
+    /// Stops the listener, which returns the error
+    Stop,
+    /// Passes the error to a callback and keeps going
+    Callback(Arc<dyn Fn(EventKind, HyprError) + Send + Sync>),
+}
+
+impl std::fmt::Debug for ErrorPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Log => write!(f, "Log"),
+            Self::Ignore => write!(f, "Ignore"),
+            Self::Stop => write!(f, "Stop"),
+            Self::Callback(_) => write!(f, "Callback(..)"),
+        }
+    }
+}
+
+impl ErrorPolicy {
+    /// Creates a policy passing errors to a callback
+    pub fn callback(f: impl Fn(EventKind, HyprError) + Send + Sync + 'static) -> Self {
+        Self::Callback(Arc::new(f))
+    }
+
+    /// Handles the result of a handler or middleware, returning an error if the listener should stop
+    pub(crate) fn handle(&self, kind: EventKind, result: crate::Result<()>) -> crate::Result<()> {
+        let Err(error) = result else {
+            return Ok(());
+        };
+        match self {
+            Self::Log => eprintln!("hyprland-rs: handling a {kind:?} event failed: {error}"),
+            Self::Ignore => {}
+            Self::Stop => return Err(error),
+            Self::Callback(f) => f(kind, error),
+        }
+        Ok(())
+    }
+}
+
+/// Runs around the handlers of every event a listener receives, for logging, timing or
+/// filtering events
+///
+/// Closures taking an [Event] and returning an `Option<Event>` are middleware filtering and
+/// changing events
+///
+/// ```rust
+/// use hyprland::event_listener::{Event, EventKind, EventListener, Middleware};
+/// use std::time::Duration;
+///
+/// struct Timing;
+///
+/// impl Middleware for Timing {
+///     fn after(&self, kind: EventKind, elapsed: Duration, _: &hyprland::Result<()>) {
+///         println!("{kind:?} handlers took {elapsed:?}");
+///     }
+/// }
+///
+/// let listener = EventListener::new();
+/// listener.add_middleware(Timing);
+/// // drops title changes before they reach any handler
+/// listener.add_middleware(|event: Event| match event {
+///     Event::WindowTitleChanged(_) | Event::WindowTitleChangedV1(_) => None,
+///     event => Some(event),
+/// });
+/// ```
+pub trait Middleware {
+    /// Runs before [Middleware::before], errors are handled by the [ErrorPolicy] of the listener
+    /// like the errors of handlers
+    fn inspect(&self, event: &Event) -> crate::Result<()> {
+        let _ = event;
+        Ok(())
+    }
+
+    /// Runs before the handlers, returning [None] drops the event
+    fn before(&self, event: Event) -> Option<Event> {
+        Some(event)
+    }
+
+    /// Runs after the handlers of an event, with how long they took and what the listener
+    /// returns for the event
+    fn after(&self, kind: EventKind, elapsed: Duration, result: &crate::Result<()>) {
+        let _ = (kind, elapsed, result);
+    }
+}
+
+impl<F: Fn(Event) -> Option<Event>> Middleware for F {
+    fn before(&self, event: Event) -> Option<Event> {
+        self(event)
+    }
+}
+
+/// Runs the middleware before the handlers, in the order it was added
+pub(crate) fn before_handlers<M>(
+    middleware: &[M],
+    policy: &ErrorPolicy,
+    mut event: Event,
+) -> crate::Result<Option<Event>>
+where
+    M: Deref<Target: Middleware>,
+{
+    for middleware in middleware {
+        policy.handle(event.kind(), middleware.inspect(&event))?;
+        match middleware.before(event) {
+            Some(next) => event = next,
+            None => return Ok(None),
+        }
+    }
+    Ok(Some(event))
+}
+
+/// Runs the middleware after the handlers, in the reverse order it was added
+pub(crate) fn after_handlers<M>(
+    middleware: &[M],
+    kind: EventKind,
+    elapsed: Duration,
+    result: &crate::Result<()>,
+) where
+    M: Deref<Target: Middleware>,
+{
+    for middleware in middleware.iter().rev() {
+        middleware.after(kind, elapsed, result);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::rc::Rc;
+    use std::sync::Mutex;
+
+    #[test]
+    fn test_error_policy() {
+        let error = || Err(HyprError::Other("failed".to_string()));
+        assert!(ErrorPolicy::Log.handle(EventKind::Bell, error()).is_ok());
+        assert!(ErrorPolicy::Ignore.handle(EventKind::Bell, error()).is_ok());
+        assert!(ErrorPolicy::Stop.handle(EventKind::Bell, error()).is_err());
+        assert!(ErrorPolicy::Stop.handle(EventKind::Bell, Ok(())).is_ok());
+
+        let errors = Arc::new(Mutex::new(vec![]));
+        let seen = errors.clone();
+        let policy = ErrorPolicy::callback(move |kind, error| {
+            if let Ok(mut seen) = seen.lock() {
+                seen.push(format!("{kind:?}: {error}"));
+            }
+        });
+        assert!(policy.handle(EventKind::Bell, error()).is_ok());
+        assert_eq!(
+            *errors.lock().unwrap_or_else(|e| e.into_inner()),
+            ["Bell: failed"]
+        );
+    }
+
+    #[test]
+    fn test_middleware_order() {
+        let middleware: Vec<Rc<dyn Middleware>> = vec![
+            Rc::new(|event| match event {
+                Event::SubMapChanged(name) => Some(Event::SubMapChanged(name + "!")),
+                event => Some(event),
+            }),
+            Rc::new(|event| match event {
+                Event::ConfigReloaded => None,
+                event => Some(event),
+            }),
+        ];
+        let policy = ErrorPolicy::Stop;
+        assert_eq!(
+            before_handlers(
+                &middleware,
+                &policy,
+                Event::SubMapChanged("resize".to_string())
+            )
+            .ok(),
+            Some(Some(Event::SubMapChanged("resize!".to_string())))
+        );
+        assert_eq!(
+            before_handlers(&middleware, &policy, Event::ConfigReloaded).ok(),
+            Some(None)
+        );
+    }
+}
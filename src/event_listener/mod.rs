@@ -1,7 +1,13 @@
 //! # Event Listener Module
 //! for documentation go to:
 //! * [EventStream] for the event listener implementation based on the [futures_lite::Stream] api
+//! * `EventEnricher` for attaching the clients and workspaces events refer to
+//! * `DerivedEventsExt` for debounced, coalesced and composite events
+//! * [EventHub] for sharing one event socket connection between several [futures_lite::Stream]s
 //! * [EventListener] for the normal [Fn] based event listener
+//! * [BlockingEvents] for a blocking [Iterator] over the events
+//! * [EventRecorder] for recording events to JSONL, replayed with `EventStream::replay`
+//! * [SendEventListener] for the [Fn] based event listener which is [Send] and [Sync], using [Send] + [Sync] closures
 //! * [AsyncEventListener] for the [Fn] based event listener which uses closures that return [std::future::Future]s
 
 #[macro_use]
@@ -10,19 +16,55 @@ mod macros;
 mod shared;
 pub use crate::event_listener::shared::*;
 
+mod middleware;
+pub use crate::event_listener::middleware::{ErrorPolicy, HandlerResult, Middleware};
+pub(crate) use crate::event_listener::middleware::{after_handlers, before_handlers};
+
+mod shutdown;
+pub use crate::event_listener::shutdown::ShutdownHandle;
+pub(crate) use crate::event_listener::shutdown::*;
+
+mod record;
+pub use crate::event_listener::record::{
+    EventRecorder, RecordedEvent, ReplayTiming, read_recording,
+};
+
+mod blocking;
+pub use crate::event_listener::blocking::BlockingEvents;
+
 mod immutable;
-pub use crate::event_listener::immutable::EventListener;
+pub use crate::event_listener::immutable::{EventListener, HandlerGuard};
+
+mod send_im;
+pub use crate::event_listener::send_im::{SendEventListener, SendHandlerGuard};
 
 #[cfg(any(feature = "async-lite", feature = "tokio"))]
 mod async_im;
 #[cfg(any(feature = "async-lite", feature = "tokio"))]
-pub use crate::event_listener::async_im::AsyncEventListener;
+pub use crate::event_listener::async_im::{AsyncEventListener, AsyncHandlerGuard};
 
 #[cfg(any(feature = "async-lite", feature = "tokio"))]
 mod stream;
 #[cfg(any(feature = "async-lite", feature = "tokio"))]
 pub use crate::event_listener::stream::EventStream;
-use crate::shared::Address;
+
+#[cfg(any(feature = "async-lite", feature = "tokio"))]
+mod enrich;
+#[cfg(any(feature = "async-lite", feature = "tokio"))]
+pub use crate::event_listener::enrich::{EnrichedEvent, EnrichedEventStream, EventEnricher};
+
+#[cfg(any(feature = "async-lite", feature = "tokio"))]
+mod derived;
+#[cfg(any(feature = "async-lite", feature = "tokio"))]
+pub use crate::event_listener::derived::{
+    DeriveOptions, DerivedEvent, DerivedEventsExt, DerivedStream, FocusChange, MonitorHotplugData,
+};
+
+#[cfg(any(feature = "async-lite", feature = "tokio"))]
+mod hub;
+#[cfg(any(feature = "async-lite", feature = "tokio"))]
+pub use crate::event_listener::hub::{DEFAULT_CAPACITY, EventHub, LagPolicy, Subscription};
+use crate::shared::{Address, WorkspaceType};
 
 // generates code for the closure based event listeners
 events! {
@@ -56,5 +98,42 @@ events! {
     GroupToggled => GroupToggledEventData, "a group was toggled", "the group toggle state was set to" => data;
     WindowMovedIntoGroup => Address, "a window was moved into a group", "a window was moved into a group with the address of" => addr;
     WindowMovedOutOfGroup => Address, "a window was moved out of a group", "a window was moved out of a group with the address of" => addr;
+    WorkspaceChangedV1 => WorkspaceType, "on workspace change (v1)", "changed workspace to" => name;
+    WorkspaceAddedV1 => WorkspaceType, "a workspace is created (v1)", "workspace was added" => name;
+    WorkspaceDeletedV1 => WorkspaceType, "a workspace is destroyed (v1)", "a workspace was destroyed" => name;
+    WorkspaceMovedV1 => WorkspaceMovedV1EventData, "a workspace is moved (v1)", "workspace was moved" => data;
+    ActiveMonitorChangedV2 => MonitorFocusedEventData, "the active monitor is changed (v2)", "Active monitor changed to" => data;
+    MonitorAddedV1 => String, "a new monitor is added (v1)", "Monitor added" => data;
+    MonitorRemovedV2 => MonitorAddedEventData, "a monitor is removed (v2)", "Monitor removed" => data;
+    WindowMovedV1 => WindowMoveV1Event, "a window is moved (v1)", "Window moved" => data;
+    WindowTitleChangedV1 => Address, "a window title is changed (v1)", "A window title changed" => addr;
+    ActiveSpecialChanged => ActiveSpecialEventData, "a monitor's special workspace is changed or closed", "Special Workspace changed" => data;
+    WindowMinimized => WindowMinimizeEventData, "a window requests to be minimized", "window minimize was set to" => data;
+    Bell => Option<Address>, "a window rings the bell", "bell rung by" => addr;
+    Custom => String, "a custom event is sent", "custom event" => data;
     Unknown => UnknownEventData, "the state of some unknown event changed", "unknown state changed to" => value
 }
+
+// maps the event data used by a single kind of event back to it
+event_data! {
+    WorkspaceMoved => WorkspaceMovedEventData;
+    WorkspaceRenamed => NonSpecialWorkspaceEventData;
+    ActiveMonitorChanged => MonitorEventData;
+    ActiveWindowChanged => Option<WindowEventData>;
+    WindowOpened => WindowOpenEvent;
+    WindowMoved => WindowMoveEvent;
+    ChangedSpecial => ChangedSpecialEventData;
+    LayoutChanged => LayoutEvent;
+    FloatStateChanged => WindowFloatEventData;
+    WindowTitleChanged => WindowTitleEventData;
+    Screencast => ScreencastEventData;
+    WindowPinned => WindowPinEventData;
+    GroupToggled => GroupToggledEventData;
+    WorkspaceMovedV1 => WorkspaceMovedV1EventData;
+    ActiveMonitorChangedV2 => MonitorFocusedEventData;
+    WindowMovedV1 => WindowMoveV1Event;
+    ActiveSpecialChanged => ActiveSpecialEventData;
+    WindowMinimized => WindowMinimizeEventData;
+    Bell => Option<Address>;
+    Unknown => UnknownEventData
+}
@@ -0,0 +1,299 @@
+use super::*;
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufRead, BufWriter, Write};
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// An event and when it was received, one line of a recording made by [EventRecorder]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedEvent {
+    /// When the event was received, in milliseconds since the unix epoch
+    pub timestamp_ms: u64,
+    /// The event
+    pub event: Event,
+}
+
+/// Records events as JSONL, one [RecordedEvent] per line
+///
+/// Recordings are replayed with [EventStream::replay][crate::event_listener::EventStream::replay].
+/// The recorder is [Middleware], so it can record everything a listener receives.
+/// Failing to record an event is handled by the [ErrorPolicy] of the listener
+///
+/// ```rust, no_run
+/// use hyprland::event_listener::{EventListener, EventRecorder};
+///
+/// let mut listener = EventListener::new();
+/// listener.add_middleware(EventRecorder::create("events.jsonl")?);
+/// listener.start_listener()?;
+/// # Ok::<(), hyprland::error::HyprError>(())
+/// ```
+#[derive(Debug)]
+pub struct EventRecorder<W> {
+    writer: Mutex<W>,
+}
+
+impl EventRecorder<BufWriter<File>> {
+    /// Creates a recorder writing to a file, replacing it if it exists
+    pub fn create(path: impl AsRef<Path>) -> crate::Result<Self> {
+        Ok(Self::new(BufWriter::new(File::create(path)?)))
+    }
+}
+
+impl<W: Write> EventRecorder<W> {
+    /// Creates a recorder writing to `writer`
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer: Mutex::new(writer),
+        }
+    }
+
+    /// Records an event received now
+    pub fn record(&self, event: &Event) -> crate::Result<()> {
+        let timestamp_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|time| time.as_millis() as u64)
+            .unwrap_or_default();
+        self.record_at(timestamp_ms, event)
+    }
+
+    /// Records an event received at `timestamp_ms`, in milliseconds since the unix epoch
+    pub fn record_at(&self, timestamp_ms: u64, event: &Event) -> crate::Result<()> {
+        let mut line = serde_json::to_vec(&RecordedEvent {
+            timestamp_ms,
+            event: event.clone(),
+        })?;
+        line.push(b'\n');
+        let mut writer = self.writer.lock().unwrap_or_else(|e| e.into_inner());
+        writer.write_all(&line)?;
+        // flushed for every event so a crash doesn't lose the events leading up to it
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Returns the writer of the recorder
+    pub fn into_inner(self) -> W {
+        self.writer.into_inner().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl<W: Write> Middleware for EventRecorder<W> {
+    fn inspect(&self, event: &Event) -> crate::Result<()> {
+        self.record(event)
+    }
+}
+
+/// Reads the events of a recording made by [EventRecorder], skipping empty lines
+pub fn read_recording(
+    recording: impl BufRead,
+) -> impl Iterator<Item = crate::Result<RecordedEvent>> {
+    recording.lines().filter_map(|line| match line {
+        Ok(line) if line.trim().is_empty() => None,
+        Ok(line) => Some(serde_json::from_str(&line).map_err(Into::into)),
+        Err(e) => Some(Err(e.into())),
+    })
+}
+
+/// How fast [EventStream::replay][crate::event_listener::EventStream::replay] yields the
+/// recorded events
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReplayTiming {
+    /// Waits between the events as long as they were apart when they were recorded
+    Original,
+    /// Yields the events as fast as possible
+    #[default]
+    Immediate,
+}
+
+#[cfg(any(feature = "async-lite", feature = "tokio"))]
+mod replay {
+    use super::*;
+    use std::io::Read;
+    use std::time::Duration;
+
+    impl EventStream {
+        /// Replays a recording made by [EventRecorder]
+        ///
+        /// The recording is read with blocking reads, so it should be a file or in memory
+        ///
+        /// ```rust, no_run
+        /// use hyprland::event_listener::{EventStream, ReplayTiming};
+        /// use futures_lite::StreamExt;
+        /// use std::{fs::File, io::BufReader};
+        ///
+        /// #[tokio::main(flavor = "current_thread")]
+        /// async fn main() -> hyprland::Result<()> {
+        ///     let recording = BufReader::new(File::open("events.jsonl")?);
+        ///     let mut stream = EventStream::replay(recording, ReplayTiming::Original);
+        ///     while let Some(event) = stream.next().await {
+        ///         println!("{:?}", event?);
+        ///     }
+        ///     Ok(())
+        /// }
+        /// ```
+        pub fn replay(recording: impl BufRead + Send + 'static, timing: ReplayTiming) -> Self {
+            Self::from_filtered(move |kinds| {
+                async_stream::try_stream! {
+                    let mut active_windows = vec![];
+                    let mut last = None;
+                    for recorded in read_recording(recording) {
+                        let RecordedEvent { timestamp_ms, event } = recorded?;
+                        if let (ReplayTiming::Original, Some(last)) = (timing, last) {
+                            let wait = Duration::from_millis(timestamp_ms.saturating_sub(last));
+                            crate::async_import::sleep(wait).await;
+                        }
+                        last = Some(timestamp_ms);
+                        if !kinds.contains(event.kind()) {
+                            continue;
+                        }
+                        for primed_event in event_primer_noexec(event, &mut active_windows)? {
+                            yield primed_event;
+                        }
+                    }
+                }
+            })
+        }
+
+        /// Replays a raw capture of the event socket, like one made with
+        /// `socat -u UNIX-CONNECT:$XDG_RUNTIME_DIR/hypr/$HYPRLAND_INSTANCE_SIGNATURE/.socket2.sock -`
+        ///
+        /// Raw captures have no timestamps, so the events are yielded as fast as possible.
+        /// The capture is read with blocking reads, so it should be a file or in memory
+        pub fn replay_raw(mut capture: impl Read + Send + 'static) -> Self {
+            Self::from_filtered(move |kinds| {
+                async_stream::try_stream! {
+                    let mut active_windows = vec![];
+                    let mut lines = EventLineBuffer::new();
+                    loop {
+                        let mut buffer = [0; 4096];
+                        let bytes_read = capture.read(&mut buffer)?;
+                        for line in lines.push(&buffer[..bytes_read]) {
+                            for event in event_parser(&line)? {
+                                if !kinds.contains(event.kind()) {
+                                    continue;
+                                }
+                                for primed_event in event_primer_noexec(event, &mut active_windows)? {
+                                    yield primed_event;
+                                }
+                            }
+                        }
+                        if bytes_read == 0 {
+                            break;
+                        }
+                    }
+                }
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recording() -> crate::Result<Vec<u8>> {
+        let recorder = EventRecorder::new(vec![]);
+        recorder.record_at(
+            1000,
+            &Event::WorkspaceChanged(WorkspaceEventData {
+                name: crate::shared::WorkspaceType::Special(Some("scratch".to_string())),
+                id: -98,
+            }),
+        )?;
+        recorder.record_at(1040, &Event::SubMapChanged("resize".to_string()))?;
+        recorder.record_at(1060, &Event::ConfigReloaded)?;
+        Ok(recorder.into_inner())
+    }
+
+    #[test]
+    fn test_recording_round_trip() -> crate::Result<()> {
+        let recording = recording()?;
+        assert_eq!(
+            String::from_utf8(recording.clone())?.lines().next(),
+            Some(
+                r#"{"timestamp_ms":1000,"event":{"WorkspaceChanged":{"name":"special:scratch","id":-98}}}"#
+            )
+        );
+        let events = read_recording(&recording[..]).collect::<crate::Result<Vec<_>>>()?;
+        assert_eq!(events.len(), 3);
+        assert_eq!(
+            events[0].event,
+            Event::WorkspaceChanged(WorkspaceEventData {
+                name: crate::shared::WorkspaceType::Special(Some("scratch".to_string())),
+                id: -98,
+            })
+        );
+        assert_eq!(events[2].timestamp_ms, 1060);
+        Ok(())
+    }
+
+    #[test]
+    fn test_recorder_errors() {
+        struct Full;
+
+        impl Write for Full {
+            fn write(&mut self, _: &[u8]) -> std::io::Result<usize> {
+                Err(std::io::ErrorKind::StorageFull.into())
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let mut listener = EventListener::new();
+        listener.add_middleware(EventRecorder::new(Full));
+        let mut active_windows = vec![];
+        assert!(
+            listener
+                .event_primer(Event::ConfigReloaded, &mut active_windows)
+                .is_ok()
+        );
+        listener.set_error_policy(ErrorPolicy::Stop);
+        assert!(
+            listener
+                .event_primer(Event::ConfigReloaded, &mut active_windows)
+                .is_err()
+        );
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test(flavor = "current_thread")]
+    async fn test_replay() -> crate::Result<()> {
+        use futures_lite::StreamExt;
+        use std::io::Cursor;
+        use std::time::{Duration, Instant};
+
+        let start = Instant::now();
+        let events = EventStream::replay(Cursor::new(recording()?), ReplayTiming::Original)
+            .filter_kinds(&[EventKind::SubMapChanged, EventKind::ConfigReloaded])
+            .try_collect::<_, _, Vec<_>>()
+            .await?;
+        assert!(start.elapsed() >= Duration::from_millis(60));
+        assert_eq!(
+            events,
+            [
+                Event::SubMapChanged("resize".to_string()),
+                Event::ConfigReloaded
+            ]
+        );
+
+        let capture = "activewindow>>kitty,~\nactivewindowv2>>5d3a1f80\nsubmap>>resize";
+        let events = EventStream::replay_raw(Cursor::new(capture))
+            .try_collect::<_, _, Vec<_>>()
+            .await?;
+        assert_eq!(
+            events,
+            [
+                Event::ActiveWindowChanged(Some(WindowEventData {
+                    class: "kitty".to_string(),
+                    title: "~".to_string(),
+                    address: crate::shared::Address::new("5d3a1f80"),
+                })),
+                Event::SubMapChanged("resize".to_string()),
+            ]
+        );
+        Ok(())
+    }
+}
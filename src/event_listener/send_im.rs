@@ -0,0 +1,274 @@
+use super::*;
+use crate::default_instance;
+use crate::instance::Instance;
+use std::sync::{Arc, Mutex, MutexGuard, Weak};
+use std::time::Instant;
+
+/// This struct is used for adding event handlers and executing them on events
+/// # The Send Event Listener
+///
+/// Works like [EventListener], but its handlers have to be [Send] + [Sync], which makes the
+/// listener itself [Send] + [Sync]. It can be moved to a worker thread or spawned as a task
+///
+/// ## Usage
+///
+/// ```rust, no_run
+/// # use hyprland::event_listener;
+/// let listener = event_listener::SendEventListener::new();
+/// listener.add_workspace_changed_handler(|data| println!("{:#?}", data));
+/// let mut worker = listener.clone();
+/// let thread = std::thread::spawn(move || worker.start_listener());
+/// // handlers can still be added from here
+/// listener.add_window_opened_handler(|data| println!("{:#?}", data));
+/// # let _ = thread.join();
+/// ```
+///
+/// ```rust, no_run
+/// # use hyprland::event_listener;
+/// #[tokio::main(flavor = "current_thread")]
+/// async fn function() -> hyprland::Result<()> {
+///     let mut listener = event_listener::SendEventListener::new();
+///     listener.add_workspace_changed_handler(|data| println!("{:#?}", data));
+///     let task = tokio::spawn(async move { listener.start_listener_async().await });
+///     task.await.map_err(|e| hyprland::error::HyprError::Other(e.to_string()))?
+/// }
+/// ```
+///
+/// ## Handlers
+///
+/// Adding a handler returns a [HandlerId] that can remove it again. Clones of the listener share
+/// its handlers, so a clone moved into a handler can add or remove handlers while the listener
+/// runs. Changes made while an event is handled apply from the next event
+///
+/// Handlers can return a [crate::Result]. Errors are handled by the [ErrorPolicy] set with
+/// [SendEventListener::set_error_policy], and [Middleware] added with [SendEventListener::add_middleware] runs
+/// around the handlers of every event
+#[derive(Clone)]
+pub struct SendEventListener {
+    pub(crate) events: Arc<Mutex<SendEvents>>,
+    shutdown: ShutdownHandle,
+}
+
+/// Removes a handler from a [SendEventListener] when dropped, see [SendEventListener::guard]
+#[must_use = "the handler is removed when the guard is dropped"]
+pub struct SendHandlerGuard {
+    id: HandlerId,
+    events: Weak<Mutex<SendEvents>>,
+}
+
+impl SendHandlerGuard {
+    /// Returns the id of the handler
+    pub fn id(&self) -> HandlerId {
+        self.id
+    }
+
+    /// Keeps the handler, dropping the guard without removing it
+    pub fn disarm(mut self) -> HandlerId {
+        self.events = Weak::new();
+        self.id
+    }
+}
+
+impl Drop for SendHandlerGuard {
+    fn drop(&mut self) {
+        if let Some(events) = self.events.upgrade() {
+            lock(&events).remove(self.id);
+        }
+    }
+}
+
+fn lock(events: &Mutex<SendEvents>) -> MutexGuard<'_, SendEvents> {
+    // handlers run without the lock held, so a poisoned lock still holds valid handlers
+    events.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+impl Default for SendEventListener {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SendEventListener {
+    /// This method creates a new SendEventListener instance
+    ///
+    /// ```rust
+    /// use hyprland::event_listener;
+    /// let mut listener = event_listener::SendEventListener::new();
+    /// ```
+    pub fn new() -> Self {
+        Self {
+            events: Arc::new(Mutex::new(create_events_send())),
+            shutdown: ShutdownHandle::new(),
+        }
+    }
+
+    pub(crate) fn lock_events(&self) -> MutexGuard<'_, SendEvents> {
+        lock(&self.events)
+    }
+
+    /// Returns a handle that stops the listener from another thread or task
+    pub fn shutdown_handle(&self) -> ShutdownHandle {
+        self.shutdown.clone()
+    }
+
+    /// Removes a handler, returning if it was found
+    pub fn remove_handler(&self, id: HandlerId) -> bool {
+        self.lock_events().remove(id)
+    }
+
+    /// Adds middleware running around the handlers of every event, in the order it was added
+    pub fn add_middleware(&self, middleware: impl Middleware + Send + Sync + 'static) {
+        self.lock_events().middleware.push(Arc::new(middleware));
+    }
+
+    /// Sets what happens when a handler returns an error
+    ///
+    /// By default ([ErrorPolicy::Log]) the errors are printed to stderr
+    pub fn set_error_policy(&self, policy: ErrorPolicy) {
+        self.lock_events().error_policy = policy;
+    }
+
+    /// Returns a guard that removes a handler when dropped
+    pub fn guard(&self, id: HandlerId) -> SendHandlerGuard {
+        SendHandlerGuard {
+            id,
+            events: Arc::downgrade(&self.events),
+        }
+    }
+
+    /// This method starts the event listener (blocking)
+    ///
+    /// This should be ran after all of your handlers are defined
+    pub fn start_listener(&mut self) -> crate::Result<()> {
+        self.instance_start_listener(default_instance()?)
+    }
+
+    /// This method starts the event listener (blocking)
+    ///
+    /// This should be ran after all of your handlers are defined
+    pub fn instance_start_listener(&mut self, instance: &Instance) -> crate::Result<()> {
+        self.instance_run_until(instance, None)?;
+        Ok(())
+    }
+
+    /// Runs the event listener until the deadline passes, it is shut down or the socket closes,
+    /// returning the fully read events it didn't handle, see [ShutdownHandle] (blocking)
+    ///
+    /// Pass [None] to run without a deadline
+    pub fn run_until(&mut self, deadline: impl Into<Option<Instant>>) -> crate::Result<Vec<Event>> {
+        self.instance_run_until(default_instance()?, deadline)
+    }
+
+    /// Runs the event listener until the deadline passes, it is shut down or the socket closes,
+    /// returning the fully read events it didn't handle, see [ShutdownHandle] (blocking)
+    ///
+    /// Pass [None] to run without a deadline
+    pub fn instance_run_until(
+        &mut self,
+        instance: &Instance,
+        deadline: impl Into<Option<Instant>>,
+    ) -> crate::Result<Vec<Event>> {
+        let shutdown = self.shutdown.clone();
+        run_blocking(self, instance, &shutdown, deadline.into())
+    }
+
+    /// This method starts the event listener (async)
+    ///
+    /// This should be ran after all of your handlers are defined
+    #[cfg(any(feature = "async-lite", feature = "tokio"))]
+    pub async fn start_listener_async(&mut self) -> crate::Result<()> {
+        self.instance_start_listener_async(default_instance()?)
+            .await
+    }
+
+    /// This method starts the event listener (async)
+    ///
+    /// This should be ran after all of your handlers are defined
+    #[cfg(any(feature = "async-lite", feature = "tokio"))]
+    pub async fn instance_start_listener_async(
+        &mut self,
+        instance: &Instance,
+    ) -> crate::Result<()> {
+        self.instance_run_until_async(instance, None).await?;
+        Ok(())
+    }
+
+    /// Runs the event listener until the deadline passes, it is shut down or the socket closes,
+    /// returning the fully read events it didn't handle, see [ShutdownHandle] (async)
+    ///
+    /// Pass [None] to run without a deadline
+    #[cfg(any(feature = "async-lite", feature = "tokio"))]
+    pub async fn run_until_async(
+        &mut self,
+        deadline: impl Into<Option<Instant>>,
+    ) -> crate::Result<Vec<Event>> {
+        self.instance_run_until_async(default_instance()?, deadline)
+            .await
+    }
+
+    /// Runs the event listener until the deadline passes, it is shut down or the socket closes,
+    /// returning the fully read events it didn't handle, see [ShutdownHandle] (async)
+    ///
+    /// Pass [None] to run without a deadline
+    #[cfg(any(feature = "async-lite", feature = "tokio"))]
+    pub async fn instance_run_until_async(
+        &mut self,
+        instance: &Instance,
+        deadline: impl Into<Option<Instant>>,
+    ) -> crate::Result<Vec<Event>> {
+        let shutdown = self.shutdown.clone();
+        run_async(self, instance, &shutdown, deadline.into()).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn assert_send_sync<T: Send + Sync>(_: &T) {}
+
+    #[test]
+    fn test_send_handlers() -> crate::Result<()> {
+        let mut listener = SendEventListener::new();
+        assert_send_sync(&listener);
+        let count = Arc::new(AtomicUsize::new(0));
+
+        let counter = count.clone();
+        let id = listener.add_sub_map_changed_handler(move |_| {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+        let counter = count.clone();
+        listener.add_config_reloaded_handler_once(move || {
+            counter.fetch_add(10, Ordering::SeqCst);
+        });
+
+        let mut worker = listener.clone();
+        let thread = std::thread::spawn(move || -> crate::Result<()> {
+            worker.event_executor(Event::SubMapChanged("resize".to_string()))?;
+            worker.event_executor(Event::ConfigReloaded)?;
+            worker.event_executor(Event::ConfigReloaded)
+        });
+        match thread.join() {
+            Ok(result) => result?,
+            Err(_) => panic!("the listener thread panicked"),
+        }
+        assert_eq!(count.load(Ordering::SeqCst), 11);
+
+        assert!(listener.remove_handler(id));
+        let guard = listener.guard(listener.add_sub_map_changed_handler(|_| {}));
+        drop(guard);
+        listener.event_executor(Event::SubMapChanged("reset".to_string()))?;
+        assert_eq!(count.load(Ordering::SeqCst), 11);
+        assert!(listener.lock_events().sub_map_changed_events.is_empty());
+        Ok(())
+    }
+
+    #[cfg(any(feature = "async-lite", feature = "tokio"))]
+    #[test]
+    fn test_send_future() {
+        fn assert_send<T: Send>(_: T) {}
+        let mut listener = SendEventListener::new();
+        assert_send(listener.start_listener_async());
+    }
+}
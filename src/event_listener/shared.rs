@@ -1,4 +1,6 @@
+use super::{EventKind, HandlerResult};
 use crate::shared::*;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::{fmt::Debug, pin::Pin};
 
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
@@ -72,20 +74,6 @@ pub(crate) trait HasExecutor {
     }
 }
 
-const BLACKLISTED_UNKNOWN_EVENTS: &[&str; 10] = &[
-    "workspace",
-    "focusedmon",
-    "monitorremoved",
-    "monitoradded",
-    "createworkspace",
-    "destroyworkspace",
-    "moveworkspace",
-    "activespecial",
-    "movewindow",
-    "windowtitle",
-];
-
-#[cfg(any(feature = "async-lite", feature = "tokio"))]
 pub(crate) fn event_primer_noexec(
     event: Event,
     abuf: &mut Vec<ActiveWindowState>,
@@ -129,10 +117,6 @@ pub(crate) fn event_primer_noexec(
         for index in to_remove.into_iter().rev() {
             abuf.swap_remove(index);
         }
-    } else if let Event::Unknown(ref data) = event {
-        if !BLACKLISTED_UNKNOWN_EVENTS.contains(&&*data.name) {
-            events.push(event);
-        }
     } else {
         events.push(event);
     }
@@ -228,20 +212,69 @@ pub(crate) fn into<T>(from: Option<(T, T)>) -> (ActiveWindowValue<T>, ActiveWind
     }
 }
 
-pub(crate) type EventType<T> = Box<T>;
-pub(crate) type AsyncEventType<T> = Pin<Box<T>>;
+pub(crate) type EventType<T> = std::rc::Rc<T>;
+pub(crate) type AsyncEventType<T> = std::sync::Arc<T>;
+
+pub(crate) type HandlerFuture =
+    Pin<Box<dyn std::future::Future<Output = crate::Result<()>> + Send>>;
+
+pub(crate) type EmptyClosure = EventType<dyn Fn() -> crate::Result<()>>;
+pub(crate) type Closure<T> = EventType<dyn Fn(T) -> crate::Result<()>>;
+pub(crate) type AsyncClosure<T> = AsyncEventType<dyn Sync + Send + Fn(T) -> HandlerFuture>;
+pub(crate) type EmptyAsyncClosure = AsyncEventType<dyn Sync + Send + Fn() -> HandlerFuture>;
+pub(crate) type SendClosure<T> = AsyncEventType<dyn Sync + Send + Fn(T) -> crate::Result<()>>;
+pub(crate) type EmptySendClosure = AsyncEventType<dyn Sync + Send + Fn() -> crate::Result<()>>;
+pub(crate) type Handlers<F> = Vec<Handler<F>>;
+pub(crate) type Closures<T> = Handlers<Closure<T>>;
+pub(crate) type AsyncClosures<T> = Handlers<AsyncClosure<T>>;
+pub(crate) type SendClosures<T> = Handlers<SendClosure<T>>;
 
-pub(crate) type VoidFuture = Pin<Box<dyn std::future::Future<Output = ()> + Send>>;
+static NEXT_HANDLER_ID: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
 
-pub(crate) type EmptyClosure = EventType<dyn Fn()>;
-pub(crate) type Closure<T> = EventType<dyn Fn(T)>;
-pub(crate) type AsyncClosure<T> = AsyncEventType<dyn Sync + Send + Fn(T) -> VoidFuture>;
-pub(crate) type EmptyAsyncClosure = AsyncEventType<dyn Sync + Send + Fn() -> VoidFuture>;
-pub(crate) type Closures<T> = Vec<Closure<T>>;
-pub(crate) type AsyncClosures<T> = Vec<AsyncClosure<T>>;
+/// Identifies a handler added to an event listener, used to remove it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct HandlerId(u64);
+
+impl HandlerId {
+    fn next() -> Self {
+        Self(NEXT_HANDLER_ID.fetch_add(1, std::sync::atomic::Ordering::Relaxed))
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct Handler<F> {
+    pub(crate) id: HandlerId,
+    pub(crate) once: bool,
+    pub(crate) f: F,
+}
+
+pub(crate) fn push_handler<F>(handlers: &mut Handlers<F>, f: F, once: bool) -> HandlerId {
+    let id = HandlerId::next();
+    handlers.push(Handler { id, once, f });
+    id
+}
+
+pub(crate) fn remove_handler<F>(handlers: &mut Handlers<F>, id: HandlerId) -> bool {
+    let len = handlers.len();
+    handlers.retain(|handler| handler.id != id);
+    handlers.len() != len
+}
+
+/// Returns copies of the handlers to run for an event.
+/// Running copies lets handlers add and remove handlers without deadlocking
+pub(crate) fn handlers_to_run<F: Clone>(handlers: &Handlers<F>) -> Handlers<F> {
+    handlers.clone()
+}
+
+/// Removes a handler that only runs once after it ran, so the ones an error skipped stay
+pub(crate) fn handler_ran<F>(handlers: &mut Handlers<F>, handler: &Handler<F>) {
+    if handler.once {
+        remove_handler(handlers, handler.id);
+    }
+}
 
 /// Event data for screencast event
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ScreencastEventData {
     /// State/Is it turning on?
     pub turning_on: bool,
@@ -250,18 +283,29 @@ pub struct ScreencastEventData {
 }
 
 /// The data for the event executed when moving a window to a new workspace
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct WindowMoveEvent {
     /// Window address
     pub window_address: Address,
     /// the workspace id
     pub workspace_id: WorkspaceId,
     /// The workspace name
+    #[serde(with = "workspace_name")]
+    pub workspace_name: WorkspaceType,
+}
+
+/// The data for the `movewindow` event
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WindowMoveV1Event {
+    /// Window address
+    pub window_address: Address,
+    /// The workspace name
+    #[serde(with = "workspace_name")]
     pub workspace_name: WorkspaceType,
 }
 
 /// The data for the event executed when opening a new window
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct WindowOpenEvent {
     /// Window address
     pub window_address: Address,
@@ -274,7 +318,7 @@ pub struct WindowOpenEvent {
 }
 
 /// The data for the event executed when changing keyboard layouts
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct LayoutEvent {
     /// Keyboard name
     pub keyboard_name: String,
@@ -394,28 +438,129 @@ impl State {
     }
 }
 
-pub(crate) fn execute_empty_closure(f: &EmptyClosure) {
-    f();
+pub(crate) fn handler<T, R: HandlerResult>(f: impl Fn(T) -> R + 'static) -> Closure<T> {
+    EventType::new(move |data| f(data).into_result())
+}
+
+pub(crate) fn empty_handler<R: HandlerResult>(f: impl Fn() -> R + 'static) -> EmptyClosure {
+    EventType::new(move || f().into_result())
 }
 
-pub(crate) fn execute_closure<T: Clone>(f: &Closure<T>, val: T) {
-    f(val);
+pub(crate) fn handler_once<T, R: HandlerResult>(f: impl FnOnce(T) -> R + 'static) -> Closure<T> {
+    let f = std::cell::Cell::new(Some(f));
+    EventType::new(move |data| f.take().map_or(Ok(()), |f| f(data).into_result()))
 }
 
+pub(crate) fn empty_handler_once<R: HandlerResult>(
+    f: impl FnOnce() -> R + 'static,
+) -> EmptyClosure {
+    let f = std::cell::Cell::new(Some(f));
+    EventType::new(move || f.take().map_or(Ok(()), |f| f().into_result()))
+}
+
+pub(crate) fn send_handler<T, R: HandlerResult>(
+    f: impl Fn(T) -> R + Send + Sync + 'static,
+) -> SendClosure<T> {
+    AsyncEventType::new(move |data| f(data).into_result())
+}
+
+pub(crate) fn empty_send_handler<R: HandlerResult>(
+    f: impl Fn() -> R + Send + Sync + 'static,
+) -> EmptySendClosure {
+    AsyncEventType::new(move || f().into_result())
+}
+
+pub(crate) fn send_handler_once<T, R: HandlerResult>(
+    f: impl FnOnce(T) -> R + Send + 'static,
+) -> SendClosure<T> {
+    let f = std::sync::Mutex::new(Some(f));
+    AsyncEventType::new(move |data| {
+        let f = f.lock().unwrap_or_else(|e| e.into_inner()).take();
+        f.map_or(Ok(()), |f| f(data).into_result())
+    })
+}
+
+pub(crate) fn empty_send_handler_once<R: HandlerResult>(
+    f: impl FnOnce() -> R + Send + 'static,
+) -> EmptySendClosure {
+    let f = std::sync::Mutex::new(Some(f));
+    AsyncEventType::new(move || {
+        let f = f.lock().unwrap_or_else(|e| e.into_inner()).take();
+        f.map_or(Ok(()), |f| f().into_result())
+    })
+}
+
+/// Boxes an async handler, which is kept once and called by reference for every event
 #[cfg(any(feature = "async-lite", feature = "tokio"))]
-pub(crate) async fn execute_empty_closure_async(f: &EmptyAsyncClosure) {
-    f().await;
+pub(crate) fn async_handler<T, F, Fut>(f: F) -> AsyncClosure<T>
+where
+    F: Fn(T) -> Fut + Send + Sync + 'static,
+    Fut: std::future::Future<Output: HandlerResult> + Send + 'static,
+{
+    AsyncEventType::new(move |data| {
+        let future = f(data);
+        Box::pin(async move { future.await.into_result() }) as HandlerFuture
+    })
 }
 
+/// Boxes an async handler without data, see [async_handler]
 #[cfg(any(feature = "async-lite", feature = "tokio"))]
-pub(crate) async fn execute_closure_async<T>(f: &AsyncClosure<T>, val: T) {
-    f(val).await;
+pub(crate) fn empty_async_handler<F, Fut>(f: F) -> EmptyAsyncClosure
+where
+    F: Fn() -> Fut + Send + Sync + 'static,
+    Fut: std::future::Future<Output: HandlerResult> + Send + 'static,
+{
+    AsyncEventType::new(move || {
+        let future = f();
+        Box::pin(async move { future.await.into_result() }) as HandlerFuture
+    })
+}
+
+/// Boxes an async handler that runs once, see [async_handler]
+#[cfg(any(feature = "async-lite", feature = "tokio"))]
+pub(crate) fn async_handler_once<T, F, Fut>(f: F) -> AsyncClosure<T>
+where
+    F: FnOnce(T) -> Fut + Send + 'static,
+    Fut: std::future::Future<Output: HandlerResult> + Send + 'static,
+{
+    let f = std::sync::Mutex::new(Some(f));
+    AsyncEventType::new(move |data| {
+        let f = f.lock().unwrap_or_else(|e| e.into_inner()).take();
+        let future = f.map(|f| f(data));
+        Box::pin(async move {
+            match future {
+                Some(future) => future.await.into_result(),
+                None => Ok(()),
+            }
+        }) as HandlerFuture
+    })
+}
+
+/// Boxes an async handler without data that runs once, see [async_handler]
+#[cfg(any(feature = "async-lite", feature = "tokio"))]
+pub(crate) fn empty_async_handler_once<F, Fut>(f: F) -> EmptyAsyncClosure
+where
+    F: FnOnce() -> Fut + Send + 'static,
+    Fut: std::future::Future<Output: HandlerResult> + Send + 'static,
+{
+    let f = std::sync::Mutex::new(Some(f));
+    AsyncEventType::new(move || {
+        let f = f.lock().unwrap_or_else(|e| e.into_inner()).take();
+        let future = f.map(|f| f());
+        Box::pin(async move {
+            match future {
+                Some(future) => future.await.into_result(),
+                None => Ok(()),
+            }
+        }) as HandlerFuture
+    })
 }
 
 /// This struct holds workspace event data
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct WorkspaceEventData {
     /// The workspace name
+    #[serde(with = "workspace_name")]
     pub name: WorkspaceType,
     /// The window id
     pub id: WorkspaceId,
@@ -423,7 +568,7 @@ pub struct WorkspaceEventData {
 
 /// This struct holds workspace event data
 /// when the workspace cannot be special
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct NonSpecialWorkspaceEventData {
     /// The workspace name
     pub name: String,
@@ -432,9 +577,10 @@ pub struct NonSpecialWorkspaceEventData {
 }
 
 /// This struct holds workspace moved event data
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct WorkspaceMovedEventData {
     /// The workspace name
+    #[serde(with = "workspace_name")]
     pub name: WorkspaceType,
     /// The window id
     pub id: WorkspaceId,
@@ -442,8 +588,18 @@ pub struct WorkspaceMovedEventData {
     pub monitor: String,
 }
 
+/// This struct holds the data of the `moveworkspace` event
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkspaceMovedV1EventData {
+    /// The workspace name
+    #[serde(with = "workspace_name")]
+    pub name: WorkspaceType,
+    /// The monitor name
+    pub monitor: String,
+}
+
 /// This struct holds window event data
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct WindowEventData {
     /// The window class
     pub class: String,
@@ -454,16 +610,26 @@ pub struct WindowEventData {
 }
 
 /// This struct holds monitor event data
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct MonitorEventData {
     /// The monitor name
     pub monitor_name: String,
     /// The workspace name
+    #[serde(with = "workspace_name::option")]
     pub workspace_name: Option<WorkspaceType>,
 }
 
+/// This struct holds the data of the `focusedmonv2` event
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MonitorFocusedEventData {
+    /// The monitor name
+    pub monitor_name: String,
+    /// The id of the active workspace of the monitor, [None] if it has none
+    pub workspace_id: Option<WorkspaceId>,
+}
+
 /// This struct holds changed special event data
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ChangedSpecialEventData {
     /// The monitor name
     pub monitor_name: String,
@@ -471,8 +637,17 @@ pub struct ChangedSpecialEventData {
     pub workspace_name: String,
 }
 
+/// This struct holds the data of the `activespecialv2` event
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ActiveSpecialEventData {
+    /// The monitor name
+    pub monitor_name: String,
+    /// The special workspace now shown on the monitor, [None] if it was closed
+    pub workspace: Option<WorkspaceEventData>,
+}
+
 /// This struct holds monitor event data
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct MonitorAddedEventData {
     /// The monitor's id
     pub id: MonitorId,
@@ -483,7 +658,7 @@ pub struct MonitorAddedEventData {
 }
 
 /// This struct holds window float event data
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct WindowFloatEventData {
     /// The window address
     pub address: Address,
@@ -491,8 +666,17 @@ pub struct WindowFloatEventData {
     pub floating: bool,
 }
 
+/// This struct holds window minimize event data
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WindowMinimizeEventData {
+    /// The window address
+    pub address: Address,
+    /// The minimize state
+    pub minimized: bool,
+}
+
 /// This struct holds window pin event data
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct WindowPinEventData {
     /// The window address
     pub address: Address,
@@ -501,7 +685,7 @@ pub struct WindowPinEventData {
 }
 
 /// This struct holds the event data for the windowtitle changed event
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct WindowTitleEventData {
     /// The window address
     pub address: Address,
@@ -512,7 +696,7 @@ pub struct WindowTitleEventData {
 /// This struct represents an unknown event to hyprland-rs
 /// this allows you to use events that haven't been implemented in hyprland-rs.
 /// To use this use the [UnknownEventData::parse_args] method to properly get the args
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct UnknownEventData {
     /// The event's name
     pub name: String,
@@ -530,7 +714,7 @@ impl UnknownEventData {
     }
 }
 /// This struct holds the data for the [Event::GroupToggled] event
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct GroupToggledEventData {
     /// The toggle status, `false` means the group was destroyed
     pub toggled: bool,
@@ -539,7 +723,7 @@ pub struct GroupToggledEventData {
 }
 
 /// This enum holds every event type
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Event {
     /// An unknown event
     Unknown(UnknownEventData),
@@ -640,6 +824,162 @@ pub enum Event {
     /// And event that emits when a window is moved out of a group,
     /// it is the equivelant of the `moveoutofgroup`
     WindowMovedOutOfGroup(Address),
+    /// An event that emits when the current workspace is changed,
+    /// it is the equivelant of the `workspace` event,
+    /// prefer [Event::WorkspaceChanged] which also has the workspace id,
+    /// skipped unless asked for, see [EventKind::is_legacy]
+    WorkspaceChangedV1(#[serde(with = "workspace_name")] WorkspaceType),
+    /// An event that emits when a workspace is created,
+    /// it is the equivelant of the `createworkspace` event,
+    /// prefer [Event::WorkspaceAdded] which also has the workspace id,
+    /// skipped unless asked for, see [EventKind::is_legacy]
+    WorkspaceAddedV1(#[serde(with = "workspace_name")] WorkspaceType),
+    /// An event that emits when a workspace is deleted,
+    /// it is the equivelant of the `destroyworkspace` event,
+    /// prefer [Event::WorkspaceDeleted] which also has the workspace id,
+    /// skipped unless asked for, see [EventKind::is_legacy]
+    WorkspaceDeletedV1(#[serde(with = "workspace_name")] WorkspaceType),
+    /// An event that emits when a workspace is moved to another monitor,
+    /// it is the equivelant of the `moveworkspace` event,
+    /// prefer [Event::WorkspaceMoved] which also has the workspace id,
+    /// skipped unless asked for, see [EventKind::is_legacy]
+    WorkspaceMovedV1(WorkspaceMovedV1EventData),
+    /// An event that emits when the active monitor is changed,
+    /// it is the equivelant of the `focusedmonv2` event
+    ActiveMonitorChangedV2(MonitorFocusedEventData),
+    /// An event that emits when a new monitor is added/connected,
+    /// it is the equivelant of the `monitoradded` event,
+    /// prefer [Event::MonitorAdded] which also has the id and description,
+    /// skipped unless asked for, see [EventKind::is_legacy]
+    MonitorAddedV1(String),
+    /// An event that emits when a monitor is removed/disconnected,
+    /// it is the equivelant of the `monitorremovedv2` event
+    MonitorRemovedV2(MonitorAddedEventData),
+    /// An event that emits when a window is moved to a different workspace,
+    /// it is the equivelant of the `movewindow` event,
+    /// prefer [Event::WindowMoved] which also has the workspace id,
+    /// skipped unless asked for, see [EventKind::is_legacy]
+    WindowMovedV1(WindowMoveV1Event),
+    /// An event that emits when the title of a window changes,
+    /// it is the equivelant of the `windowtitle` event,
+    /// prefer [Event::WindowTitleChanged] which also has the title,
+    /// skipped unless asked for, see [EventKind::is_legacy]
+    WindowTitleChangedV1(Address),
+    /// An event that emits when the special workspace of a monitor is changed or closed,
+    /// it is the equivelant of the `activespecialv2` event
+    ActiveSpecialChanged(ActiveSpecialEventData),
+    /// An event that emits when a window requests to be minimized or unminimized,
+    /// it is the equivelant of the `minimized` event
+    WindowMinimized(WindowMinimizeEventData),
+    /// An event that emits when a window rings the bell,
+    /// it is the equivelant of the `bell` event
+    Bell(Option<Address>),
+    /// An event that emits when a custom event is sent with `hyprctl dispatch event`,
+    /// it is the equivelant of the `custom` event
+    Custom(String),
+}
+
+/// The kinds of the older events Hyprland sends next to a newer event carrying more data
+const LEGACY_KINDS: [EventKind; 7] = [
+    EventKind::WorkspaceChangedV1,
+    EventKind::WorkspaceAddedV1,
+    EventKind::WorkspaceDeletedV1,
+    EventKind::WorkspaceMovedV1,
+    EventKind::MonitorAddedV1,
+    EventKind::WindowMovedV1,
+    EventKind::WindowTitleChangedV1,
+];
+
+impl EventKind {
+    /// Whether Hyprland sends this older event next to a newer one carrying more data,
+    /// like [EventKind::WindowMovedV1] next to [EventKind::WindowMoved]
+    ///
+    /// So every action isn't seen twice, these events are skipped unless asked for, by listing
+    /// them in `filter_kinds` or by adding a handler for them to a listener
+    pub fn is_legacy(self) -> bool {
+        LEGACY_KINDS.contains(&self)
+    }
+}
+
+/// A set of [EventKind]s, one bit per kind
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct KindSet(u128);
+
+// every kind needs a bit, Unknown is the last kind
+const _: () = assert!((EventKind::Unknown as u32) < u128::BITS);
+
+impl KindSet {
+    /// Every kind but the legacy ones, see [EventKind::is_legacy]
+    pub(crate) const DEFAULT: Self = {
+        let mut bits = u128::MAX;
+        let mut index = 0;
+        while index < LEGACY_KINDS.len() {
+            bits &= !(1 << LEGACY_KINDS[index] as u32);
+            index += 1;
+        }
+        Self(bits)
+    };
+
+    pub(crate) fn from_kinds(kinds: &[EventKind]) -> Self {
+        Self(
+            kinds
+                .iter()
+                .fold(0, |bits, kind| bits | (1 << *kind as u32)),
+        )
+    }
+
+    pub(crate) fn contains(self, kind: EventKind) -> bool {
+        self.0 & (1 << kind as u32) != 0
+    }
+}
+
+impl Default for KindSet {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Event data that only a single kind of [Event] carries, see [EventStream::only][crate::event_listener::EventStream::only]
+pub trait EventData: Sized {
+    /// The kind of the events carrying this data
+    const KIND: EventKind;
+
+    /// Takes the data out of an event, if it is of [Self::KIND]
+    fn from_event(event: Event) -> Option<Self>;
+}
+
+/// (De)serializes workspace names the way Hyprland writes them, as [WorkspaceType] on its own
+/// reads named special workspaces back as regular ones
+mod workspace_name {
+    use super::*;
+
+    pub(super) fn serialize<S: Serializer>(name: &WorkspaceType, s: S) -> Result<S::Ok, S::Error> {
+        s.collect_str(name)
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<WorkspaceType, D::Error> {
+        String::deserialize(d).map(parse_string_as_work)
+    }
+
+    pub(super) mod option {
+        use super::*;
+
+        pub(in super::super) fn serialize<S: Serializer>(
+            name: &Option<WorkspaceType>,
+            s: S,
+        ) -> Result<S::Ok, S::Error> {
+            match name {
+                Some(name) => s.collect_str(name),
+                None => s.serialize_none(),
+            }
+        }
+
+        pub(in super::super) fn deserialize<'de, D: Deserializer<'de>>(
+            d: D,
+        ) -> Result<Option<WorkspaceType>, D::Error> {
+            Ok(Option::<String>::deserialize(d)?.map(parse_string_as_work))
+        }
+    }
 }
 
 fn parse_string_as_work(str: String) -> WorkspaceType {
@@ -691,6 +1031,19 @@ pub(crate) enum ParsedEventType {
     ToggleGroup,
     MoveIntoGroup,
     MoveOutOfGroup,
+    WorkspaceChangedV1,
+    WorkspaceDeletedV1,
+    WorkspaceAddedV1,
+    WorkspaceMovedV1,
+    ActiveMonitorChangedV2,
+    MonitorAddedV1,
+    MonitorRemovedV2,
+    WindowMovedV1,
+    WindowTitleChangedV1,
+    ActiveSpecialV2,
+    Minimized,
+    Bell,
+    Custom,
 }
 
 /// All Hyprland events's arg count and enum variant.
@@ -737,6 +1090,19 @@ pub(crate) static EVENTS: &[(&str, (usize, ParsedEventType))] = &[
     ("togglegroup", (2, ParsedEventType::ToggleGroup)),
     ("moveintogroup", (1, ParsedEventType::MoveIntoGroup)),
     ("moveoutofgroup", (1, ParsedEventType::MoveOutOfGroup)),
+    ("workspace", (1, ParsedEventType::WorkspaceChangedV1)),
+    ("destroyworkspace", (1, ParsedEventType::WorkspaceDeletedV1)),
+    ("createworkspace", (1, ParsedEventType::WorkspaceAddedV1)),
+    ("moveworkspace", (2, ParsedEventType::WorkspaceMovedV1)),
+    ("focusedmonv2", (2, ParsedEventType::ActiveMonitorChangedV2)),
+    ("monitoradded", (1, ParsedEventType::MonitorAddedV1)),
+    ("monitorremovedv2", (3, ParsedEventType::MonitorRemovedV2)),
+    ("movewindow", (2, ParsedEventType::WindowMovedV1)),
+    ("windowtitle", (1, ParsedEventType::WindowTitleChangedV1)),
+    ("activespecialv2", (3, ParsedEventType::ActiveSpecialV2)),
+    ("minimized", (2, ParsedEventType::Minimized)),
+    ("bell", (1, ParsedEventType::Bell)),
+    ("custom", (1, ParsedEventType::Custom)),
 ];
 
 use crate::default_instance;
@@ -798,6 +1164,49 @@ macro_rules! get {
     };
 }
 
+/// Splits the bytes read from the event socket into lines, keeping incomplete lines for the next
+/// read, so events and multibyte characters split across reads aren't broken
+#[derive(Debug, Default)]
+pub(crate) struct EventLineBuffer {
+    carry: Vec<u8>,
+}
+
+impl EventLineBuffer {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the bytes of a read, returning the decoded lines it completed.
+    /// An empty read means the stream ended, which returns the last line even without a newline
+    pub(crate) fn push(&mut self, bytes: &[u8]) -> Vec<String> {
+        let end = if bytes.is_empty() {
+            self.carry.len()
+        } else {
+            self.carry.extend_from_slice(bytes);
+            match self.carry.iter().rposition(|b| *b == b'\n') {
+                Some(end) => end + 1,
+                None => return vec![],
+            }
+        };
+        let rest = self.carry.split_off(end);
+        let complete = std::mem::replace(&mut self.carry, rest);
+        complete
+            .split(|b| *b == b'\n')
+            .filter(|line| !line.is_empty())
+            .map(crate::encoding::decode_ipc_response)
+            .collect()
+    }
+}
+
+/// Parses the lines completed by a read
+pub(crate) fn parse_lines(lines: Vec<String>) -> crate::Result<Vec<Event>> {
+    let mut events = vec![];
+    for line in lines {
+        events.extend(event_parser(&line)?);
+    }
+    Ok(events)
+}
+
 /// This internal function parses event strings
 pub(crate) fn event_parser(event: &str) -> crate::Result<Vec<Event>> {
     // TODO: Optimize nested looped regex capturing. Maybe pull in rayon if possible.
@@ -913,7 +1322,7 @@ pub(crate) fn event_parser(event: &str) -> crate::Result<Vec<Event>> {
             ParsedEventType::LayerOpened => Ok(Event::LayerOpened(get![args;0])),
             ParsedEventType::LayerClosed => Ok(Event::LayerClosed(get![args;0])),
             ParsedEventType::FloatStateChanged => {
-                let state = get![ref args;1] == "0"; // FIXME: does 0 mean it's floating?
+                let state = get![ref args;1] == "1";
                 Ok(Event::FloatStateChanged(WindowFloatEventData {
                     address: Address::new(get![ref args;0]),
                     floating: state,
@@ -957,6 +1366,75 @@ pub(crate) fn event_parser(event: &str) -> crate::Result<Vec<Event>> {
             ParsedEventType::MoveOutOfGroup => {
                 Ok(Event::WindowMovedOutOfGroup(Address::new(get![ref args;0])))
             }
+            ParsedEventType::WorkspaceChangedV1 => Ok(Event::WorkspaceChangedV1(
+                parse_string_as_work(get![args;0]),
+            )),
+            ParsedEventType::WorkspaceDeletedV1 => Ok(Event::WorkspaceDeletedV1(
+                parse_string_as_work(get![args;0]),
+            )),
+            ParsedEventType::WorkspaceAddedV1 => {
+                Ok(Event::WorkspaceAddedV1(parse_string_as_work(get![args;0])))
+            }
+            ParsedEventType::WorkspaceMovedV1 => {
+                Ok(Event::WorkspaceMovedV1(WorkspaceMovedV1EventData {
+                    name: parse_string_as_work(get![args;0]),
+                    monitor: get![args;1],
+                }))
+            }
+            ParsedEventType::ActiveMonitorChangedV2 => {
+                let workspace_id = get![ref args;1];
+                Ok(Event::ActiveMonitorChangedV2(MonitorFocusedEventData {
+                    monitor_name: get![args;0],
+                    workspace_id: if workspace_id == "?" || workspace_id.is_empty() {
+                        None
+                    } else {
+                        Some(parse_int!(workspace_id, event: "ActiveMonitorChangedV2"))
+                    },
+                }))
+            }
+            ParsedEventType::MonitorAddedV1 => Ok(Event::MonitorAddedV1(get![args;0])),
+            ParsedEventType::MonitorRemovedV2 => {
+                Ok(Event::MonitorRemovedV2(MonitorAddedEventData {
+                    id: parse_int!(get![ref args;0], event: "MonitorRemovedV2" => MonitorId),
+                    name: get![args;1],
+                    description: get![args;2],
+                }))
+            }
+            ParsedEventType::WindowMovedV1 => Ok(Event::WindowMovedV1(WindowMoveV1Event {
+                window_address: Address::fmt_new(get![ref args;0]),
+                workspace_name: parse_string_as_work(get![args;1]),
+            })),
+            ParsedEventType::WindowTitleChangedV1 => {
+                Ok(Event::WindowTitleChangedV1(Address::new(get![ref args;0])))
+            }
+            ParsedEventType::ActiveSpecialV2 => {
+                let id = get![ref args;0];
+                let workspace = if id.is_empty() {
+                    None
+                } else {
+                    Some(WorkspaceEventData {
+                        id: parse_int!(id, event: "ActiveSpecialV2"),
+                        name: parse_string_as_work(get![args;1]),
+                    })
+                };
+                Ok(Event::ActiveSpecialChanged(ActiveSpecialEventData {
+                    monitor_name: get![args;2],
+                    workspace,
+                }))
+            }
+            ParsedEventType::Minimized => Ok(Event::WindowMinimized(WindowMinimizeEventData {
+                address: Address::new(get![ref args;0]),
+                minimized: get![ref args;1] == "1",
+            })),
+            ParsedEventType::Bell => {
+                let addr = get![ref args;0];
+                Ok(Event::Bell(if addr.is_empty() {
+                    None
+                } else {
+                    Some(Address::new(addr))
+                }))
+            }
+            ParsedEventType::Custom => Ok(Event::Custom(get![args;0])),
         },
     });
 
@@ -968,3 +1446,370 @@ pub(crate) fn event_parser(event: &str) -> crate::Result<Vec<Event>> {
 
     Ok(events)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[track_caller]
+    fn check(line: &str, expected: Event) {
+        match event_parser(line) {
+            Ok(events) => assert_eq!(events, [expected], "{line}"),
+            Err(e) => panic!("Error occured: {e}"), // Note to greppers: this is in a test!
+        }
+    }
+
+    fn addr(address: &str) -> Address {
+        Address::new(address)
+    }
+
+    fn regular(name: &str) -> WorkspaceType {
+        WorkspaceType::Regular(name.to_string())
+    }
+
+    fn special(name: &str) -> WorkspaceType {
+        WorkspaceType::Special(Some(name.to_string()))
+    }
+
+    #[test]
+    fn test_fragmented_events() {
+        let input: &[u8] = b"workspacev2>>2,dev\n\
+            windowtitlev2>>5d3a1f80,caf\xc3\xa9 \xe2\x80\x94 notes\n\
+            \n\
+            windowtitlev2>>6e4b2091,tradu\xe7\xe3ocoletiva.docx\n\
+            openwindow>>7f5c3102,2,kitty,fish, the shell\n\
+            submap>>resize";
+        let expected = [
+            "workspacev2>>2,dev",
+            "windowtitlev2>>5d3a1f80,café — notes",
+            "windowtitlev2>>6e4b2091,traduçãocoletiva.docx",
+            "openwindow>>7f5c3102,2,kitty,fish, the shell",
+            "submap>>resize",
+        ];
+        for chunk_size in 1..=input.len() {
+            let mut lines = EventLineBuffer::new();
+            let mut read = vec![];
+            for chunk in input.chunks(chunk_size) {
+                read.extend(lines.push(chunk));
+            }
+            // The stream ending flushes the last line
+            read.extend(lines.push(&[]));
+            assert_eq!(read, expected, "chunk size {chunk_size}");
+        }
+
+        let mut lines = EventLineBuffer::new();
+        assert!(lines.push(b"closewindow>>5d3a").is_empty());
+        let events: Vec<Event> = lines
+            .push(b"1f80\nurgent>>")
+            .iter()
+            .flat_map(|line| event_parser(line).ok())
+            .flatten()
+            .collect();
+        assert_eq!(events, [Event::WindowClosed(addr("5d3a1f80"))]);
+    }
+
+    #[test]
+    fn test_event_kinds() {
+        let events = match event_parser(
+            "activewindow>>kitty,fish\nactivewindowv2>>5d3a1f80\nopenwindow>>5d3a1f80,2,kitty,fish\nconfigreloaded>>",
+        ) {
+            Ok(events) => events,
+            Err(e) => panic!("Error occured: {e}"), // Note to greppers: this is in a test!
+        };
+        let kinds: Vec<_> = events.iter().map(Event::kind).collect();
+        assert_eq!(
+            kinds,
+            [
+                EventKind::ActiveWindowChanged,
+                EventKind::ActiveWindowChanged,
+                EventKind::WindowOpened,
+                EventKind::ConfigReloaded,
+            ]
+        );
+        assert_eq!(WindowOpenEvent::KIND, EventKind::WindowOpened);
+        let opened: Vec<_> = events
+            .into_iter()
+            .filter_map(WindowOpenEvent::from_event)
+            .collect();
+        assert_eq!(
+            opened,
+            [WindowOpenEvent {
+                window_address: addr("5d3a1f80"),
+                workspace_name: "2".to_string(),
+                window_class: "kitty".to_string(),
+                window_title: "fish".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_workspace_events() {
+        let data = |id, name| WorkspaceEventData { id, name };
+        check(
+            "workspacev2>>2,dev",
+            Event::WorkspaceChanged(data(2, regular("dev"))),
+        );
+        check(
+            "destroyworkspacev2>>-98,special:term",
+            Event::WorkspaceDeleted(data(-98, special("term"))),
+        );
+        check(
+            "createworkspacev2>>3,3",
+            Event::WorkspaceAdded(data(3, regular("3"))),
+        );
+        check(
+            "moveworkspacev2>>3,3,DP-1",
+            Event::WorkspaceMoved(WorkspaceMovedEventData {
+                id: 3,
+                name: regular("3"),
+                monitor: "DP-1".to_string(),
+            }),
+        );
+        check(
+            "renameworkspace>>4,web, mail",
+            Event::WorkspaceRenamed(NonSpecialWorkspaceEventData {
+                id: 4,
+                name: "web, mail".to_string(),
+            }),
+        );
+        check("workspace>>dev", Event::WorkspaceChangedV1(regular("dev")));
+        check(
+            "createworkspace>>special",
+            Event::WorkspaceAddedV1(WorkspaceType::Special(None)),
+        );
+        check(
+            "destroyworkspace>>special:term",
+            Event::WorkspaceDeletedV1(special("term")),
+        );
+        check(
+            "moveworkspace>>3,DP-1",
+            Event::WorkspaceMovedV1(WorkspaceMovedV1EventData {
+                name: regular("3"),
+                monitor: "DP-1".to_string(),
+            }),
+        );
+        check(
+            "activespecial>>special:term,DP-1",
+            Event::ChangedSpecial(ChangedSpecialEventData {
+                monitor_name: "DP-1".to_string(),
+                workspace_name: "special:term".to_string(),
+            }),
+        );
+        check(
+            "activespecial>>,DP-1",
+            Event::SpecialRemoved("DP-1".to_string()),
+        );
+        check(
+            "activespecialv2>>-98,special:term,DP-1",
+            Event::ActiveSpecialChanged(ActiveSpecialEventData {
+                monitor_name: "DP-1".to_string(),
+                workspace: Some(data(-98, special("term"))),
+            }),
+        );
+        check(
+            "activespecialv2>>,,DP-1",
+            Event::ActiveSpecialChanged(ActiveSpecialEventData {
+                monitor_name: "DP-1".to_string(),
+                workspace: None,
+            }),
+        );
+    }
+
+    #[test]
+    fn test_monitor_events() {
+        check(
+            "focusedmon>>DP-1,2",
+            Event::ActiveMonitorChanged(MonitorEventData {
+                monitor_name: "DP-1".to_string(),
+                workspace_name: Some(regular("2")),
+            }),
+        );
+        check(
+            "focusedmon>>DP-1,?",
+            Event::ActiveMonitorChanged(MonitorEventData {
+                monitor_name: "DP-1".to_string(),
+                workspace_name: None,
+            }),
+        );
+        check(
+            "focusedmonv2>>DP-1,2",
+            Event::ActiveMonitorChangedV2(MonitorFocusedEventData {
+                monitor_name: "DP-1".to_string(),
+                workspace_id: Some(2),
+            }),
+        );
+        let monitor = MonitorAddedEventData {
+            id: 1,
+            name: "HDMI-A-1".to_string(),
+            description: "Dell Inc. DELL U2720Q, 4K".to_string(),
+        };
+        check(
+            "monitoraddedv2>>1,HDMI-A-1,Dell Inc. DELL U2720Q, 4K",
+            Event::MonitorAdded(monitor.clone()),
+        );
+        check(
+            "monitorremovedv2>>1,HDMI-A-1,Dell Inc. DELL U2720Q, 4K",
+            Event::MonitorRemovedV2(monitor),
+        );
+        check(
+            "monitoradded>>HDMI-A-1",
+            Event::MonitorAddedV1("HDMI-A-1".to_string()),
+        );
+        check(
+            "monitorremoved>>HDMI-A-1",
+            Event::MonitorRemoved("HDMI-A-1".to_string()),
+        );
+    }
+
+    #[test]
+    fn test_window_events() {
+        check(
+            "activewindow>>kitty,~/src, fish",
+            Event::ActiveWindowChangedV1(Some(("kitty".to_string(), "~/src, fish".to_string()))),
+        );
+        check("activewindow>>,", Event::ActiveWindowChangedV1(None));
+        check(
+            "activewindowv2>>5d3a1f80",
+            Event::ActiveWindowChangedV2(Some(addr("5d3a1f80"))),
+        );
+        check("activewindowv2>>", Event::ActiveWindowChangedV2(None));
+        check("fullscreen>>1", Event::FullscreenStateChanged(true));
+        check(
+            "openwindow>>5d3a1f80,2,kitty,fish, the shell",
+            Event::WindowOpened(WindowOpenEvent {
+                window_address: addr("5d3a1f80"),
+                workspace_name: "2".to_string(),
+                window_class: "kitty".to_string(),
+                window_title: "fish, the shell".to_string(),
+            }),
+        );
+        check(
+            "closewindow>>5d3a1f80",
+            Event::WindowClosed(addr("5d3a1f80")),
+        );
+        check(
+            "movewindowv2>>5d3a1f80,-98,special:term",
+            Event::WindowMoved(WindowMoveEvent {
+                window_address: addr("5d3a1f80"),
+                workspace_id: -98,
+                workspace_name: special("term"),
+            }),
+        );
+        check(
+            "movewindow>>5d3a1f80,special:term",
+            Event::WindowMovedV1(WindowMoveV1Event {
+                window_address: addr("5d3a1f80"),
+                workspace_name: special("term"),
+            }),
+        );
+        check(
+            "changefloatingmode>>5d3a1f80,1",
+            Event::FloatStateChanged(WindowFloatEventData {
+                address: addr("5d3a1f80"),
+                floating: true,
+            }),
+        );
+        check(
+            "changefloatingmode>>5d3a1f80,0",
+            Event::FloatStateChanged(WindowFloatEventData {
+                address: addr("5d3a1f80"),
+                floating: false,
+            }),
+        );
+        check(
+            "urgent>>5d3a1f80",
+            Event::UrgentStateChanged(addr("5d3a1f80")),
+        );
+        check(
+            "windowtitlev2>>5d3a1f80,vim, main.rs",
+            Event::WindowTitleChanged(WindowTitleEventData {
+                address: addr("5d3a1f80"),
+                title: "vim, main.rs".to_string(),
+            }),
+        );
+        check(
+            "windowtitle>>5d3a1f80",
+            Event::WindowTitleChangedV1(addr("5d3a1f80")),
+        );
+        check(
+            "pin>>5d3a1f80,1",
+            Event::WindowPinned(WindowPinEventData {
+                address: addr("5d3a1f80"),
+                pinned: true,
+            }),
+        );
+        check(
+            "minimized>>5d3a1f80,1",
+            Event::WindowMinimized(WindowMinimizeEventData {
+                address: addr("5d3a1f80"),
+                minimized: true,
+            }),
+        );
+        check("bell>>5d3a1f80", Event::Bell(Some(addr("5d3a1f80"))));
+        check("bell>>", Event::Bell(None));
+    }
+
+    #[test]
+    fn test_group_events() {
+        check(
+            "togglegroup>>1,5d3a1f80,6e4b2091",
+            Event::GroupToggled(GroupToggledEventData {
+                toggled: true,
+                window_addresses: vec![addr("5d3a1f80"), addr("6e4b2091")],
+            }),
+        );
+        check(
+            "moveintogroup>>5d3a1f80",
+            Event::WindowMovedIntoGroup(addr("5d3a1f80")),
+        );
+        check(
+            "moveoutofgroup>>5d3a1f80",
+            Event::WindowMovedOutOfGroup(addr("5d3a1f80")),
+        );
+        check(
+            "ignoregrouplock>>1",
+            Event::IgnoreGroupLockStateChanged(true),
+        );
+        check("lockgroups>>0", Event::LockGroupsStateChanged(false));
+    }
+
+    #[test]
+    fn test_misc_events() {
+        check(
+            "activelayout>>at-translated-set-2-keyboard,English (US)",
+            Event::LayoutChanged(LayoutEvent {
+                keyboard_name: "at-translated-set-2-keyboard".to_string(),
+                layout_name: "English (US)".to_string(),
+            }),
+        );
+        check("submap>>resize", Event::SubMapChanged("resize".to_string()));
+        check("submap>>", Event::SubMapChanged(String::new()));
+        check(
+            "openlayer>>waybar",
+            Event::LayerOpened("waybar".to_string()),
+        );
+        check(
+            "closelayer>>waybar",
+            Event::LayerClosed("waybar".to_string()),
+        );
+        check(
+            "screencast>>1,0",
+            Event::Screencast(ScreencastEventData {
+                turning_on: true,
+                monitor: false,
+            }),
+        );
+        check("configreloaded>>", Event::ConfigReloaded);
+        check(
+            "custom>>build done, 0 errors",
+            Event::Custom("build done, 0 errors".to_string()),
+        );
+        check(
+            "someday>>a,b",
+            Event::Unknown(UnknownEventData {
+                name: "someday".to_string(),
+                args: "a,b".to_string(),
+            }),
+        );
+    }
+}
@@ -0,0 +1,340 @@
+#[cfg(any(feature = "async-lite", feature = "tokio"))]
+use super::HasAsyncExecutor;
+use super::{
+    ActiveWindowState, Event, EventLineBuffer, HasExecutor, event_primer_noexec, parse_lines,
+};
+use crate::instance::Instance;
+use std::os::unix::net::UnixStream;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::task::Waker;
+use std::time::Instant;
+
+/// Stops a running event listener from another thread or task
+///
+/// A shutdown requested while the listener isn't running stops its next run right away.
+/// The run returns the events of the last read that weren't handled yet, merged like the handled
+/// ones. Only complete lines become events: a line that was only partly read, anything still
+/// unread on the socket and an active window event whose other half wasn't read yet are dropped
+///
+/// ```rust, no_run
+/// use hyprland::event_listener::EventListener;
+/// let mut listener = EventListener::new();
+/// listener.add_window_opened_handler(|data| println!("{data:#?}"));
+/// let handle = listener.shutdown_handle();
+/// std::thread::spawn(move || {
+///     std::thread::sleep(std::time::Duration::from_secs(10));
+///     handle.shutdown();
+/// });
+/// let undelivered = listener.run_until(None)?;
+/// # Ok::<(), hyprland::error::HyprError>(())
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ShutdownHandle {
+    state: Arc<ShutdownState>,
+}
+
+#[derive(Debug, Default)]
+struct ShutdownState {
+    requested: AtomicBool,
+    wakers: Mutex<Vec<Waker>>,
+    streams: Mutex<Vec<UnixStream>>,
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+impl ShutdownHandle {
+    /// Creates a new handle
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stops the listener after the event it is handling
+    pub fn shutdown(&self) {
+        self.state.requested.store(true, Ordering::SeqCst);
+        for waker in lock(&self.state.wakers).drain(..) {
+            waker.wake();
+        }
+        // Wakes up blocking reads
+        for stream in lock(&self.state.streams).iter() {
+            let _ = stream.shutdown(std::net::Shutdown::Read);
+        }
+    }
+
+    /// Returns if a shutdown was requested
+    pub fn is_shutdown(&self) -> bool {
+        self.state.requested.load(Ordering::SeqCst)
+    }
+
+    /// Lets a blocking read on the stream be interrupted by [ShutdownHandle::shutdown]
+    pub(crate) fn register_stream(&self, stream: &UnixStream) -> crate::Result<()> {
+        let stream = stream.try_clone()?;
+        let mut streams = lock(&self.state.streams);
+        if self.is_shutdown() {
+            let _ = stream.shutdown(std::net::Shutdown::Read);
+        }
+        streams.push(stream);
+        Ok(())
+    }
+
+    /// Clears the shutdown request once a run has stopped
+    pub(crate) fn reset(&self) {
+        lock(&self.state.streams).clear();
+        lock(&self.state.wakers).clear();
+        self.state.requested.store(false, Ordering::SeqCst);
+    }
+
+    /// Waits for a shutdown to be requested
+    #[cfg(any(feature = "async-lite", feature = "tokio"))]
+    pub(crate) async fn wait(&self) {
+        std::future::poll_fn(|cx| {
+            if self.is_shutdown() {
+                return std::task::Poll::Ready(());
+            }
+            let mut wakers = lock(&self.state.wakers);
+            if !wakers.iter().any(|waker| waker.will_wake(cx.waker())) {
+                wakers.push(cx.waker().clone());
+            }
+            drop(wakers);
+            // Checked again in case the shutdown happened before the waker was stored
+            if self.is_shutdown() {
+                std::task::Poll::Ready(())
+            } else {
+                std::task::Poll::Pending
+            }
+        })
+        .await
+    }
+}
+
+/// Reads from the event socket, returning [None] once the listener should stop
+pub(crate) fn read_blocking(
+    stream: &mut UnixStream,
+    buffer: &mut [u8],
+    shutdown: &ShutdownHandle,
+    deadline: Option<Instant>,
+) -> crate::Result<Option<usize>> {
+    use std::io::{ErrorKind, Read};
+    loop {
+        if shutdown.is_shutdown() {
+            return Ok(None);
+        }
+        if let Some(deadline) = deadline {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return Ok(None);
+            }
+            stream.set_read_timeout(Some(remaining))?;
+        }
+        match stream.read(buffer) {
+            // A shutdown closes the reading half of the stream
+            Ok(0) if shutdown.is_shutdown() => return Ok(None),
+            Ok(bytes_read) => return Ok(Some(bytes_read)),
+            Err(e) if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {}
+            Err(e) => return Err(e.into()),
+        }
+    }
+}
+
+/// Reads from the event socket, returning [None] once the listener should stop (async)
+#[cfg(any(feature = "async-lite", feature = "tokio"))]
+pub(crate) async fn read_async(
+    stream: &mut crate::async_import::UnixStream,
+    buffer: &mut [u8],
+    shutdown: &ShutdownHandle,
+    deadline: Option<Instant>,
+) -> crate::Result<Option<usize>> {
+    use crate::async_import::AsyncReadExt;
+    use futures_lite::future::{or, pending};
+    let stop = async {
+        shutdown.wait().await;
+        Ok(None)
+    };
+    let timeout = async {
+        match deadline {
+            Some(deadline) => {
+                crate::async_import::sleep(deadline.saturating_duration_since(Instant::now())).await
+            }
+            None => pending().await,
+        }
+        Ok(None)
+    };
+    let read = async { Ok(Some(stream.read(buffer).await?)) };
+    // The shutdown is polled first so it wins over pending data
+    or(stop, or(timeout, read)).await
+}
+/// Runs a listener with blocking handlers on the event socket (blocking)
+pub(crate) fn run_blocking(
+    listener: &mut impl HasExecutor,
+    instance: &Instance,
+    shutdown: &ShutdownHandle,
+    deadline: Option<Instant>,
+) -> crate::Result<Vec<Event>> {
+    let result = (|| {
+        let mut stream = instance.get_event_stream()?;
+        shutdown.register_stream(&stream)?;
+
+        let mut active_windows = vec![];
+        let mut lines = EventLineBuffer::new();
+        loop {
+            let mut buffer = [0; 4096];
+            let Some(bytes_read) = read_blocking(&mut stream, &mut buffer, shutdown, deadline)?
+            else {
+                break;
+            };
+            let mut events = parse_lines(lines.push(&buffer[..bytes_read]))?.into_iter();
+            while let Some(event) = events.next() {
+                if shutdown.is_shutdown() {
+                    return unhandled(std::iter::once(event).chain(events), &mut active_windows);
+                }
+                listener.event_primer(event, &mut active_windows)?;
+            }
+            if bytes_read == 0 {
+                // If no bytes were read, we can assume the stream is closed
+                break;
+            }
+        }
+        Ok(vec![])
+    })();
+    shutdown.reset();
+    result
+}
+
+/// Primes the fully read events that weren't handled when the listener stopped
+fn unhandled(
+    events: impl Iterator<Item = Event>,
+    active_windows: &mut Vec<ActiveWindowState>,
+) -> crate::Result<Vec<Event>> {
+    let mut primed = vec![];
+    for event in events {
+        primed.extend(event_primer_noexec(event, active_windows)?);
+    }
+    Ok(primed)
+}
+
+/// Runs a listener with blocking handlers on the event socket (async)
+#[cfg(any(feature = "async-lite", feature = "tokio"))]
+pub(crate) async fn run_async(
+    listener: &mut impl HasExecutor,
+    instance: &Instance,
+    shutdown: &ShutdownHandle,
+    deadline: Option<Instant>,
+) -> crate::Result<Vec<Event>> {
+    run_async_executor(
+        &mut BlockingExecutor(listener),
+        instance,
+        shutdown,
+        deadline,
+    )
+    .await
+}
+
+/// Runs blocking handlers from [run_async_executor]
+#[cfg(any(feature = "async-lite", feature = "tokio"))]
+struct BlockingExecutor<'a, L>(&'a mut L);
+
+#[cfg(any(feature = "async-lite", feature = "tokio"))]
+impl<L: HasExecutor> HasAsyncExecutor for BlockingExecutor<'_, L> {
+    async fn event_executor_async(&mut self, event: Event) -> crate::Result<()> {
+        self.0.event_executor(event)
+    }
+}
+
+/// Runs a listener with async handlers on the event socket (async)
+#[cfg(any(feature = "async-lite", feature = "tokio"))]
+pub(crate) async fn run_async_executor(
+    listener: &mut impl HasAsyncExecutor,
+    instance: &Instance,
+    shutdown: &ShutdownHandle,
+    deadline: Option<Instant>,
+) -> crate::Result<Vec<Event>> {
+    let result = async {
+        let mut stream = instance.get_event_stream_async().await?;
+
+        let mut active_windows = vec![];
+        let mut lines = EventLineBuffer::new();
+        loop {
+            let mut buffer = [0; 4096];
+            let Some(bytes_read) = read_async(&mut stream, &mut buffer, shutdown, deadline).await?
+            else {
+                break;
+            };
+            let mut events = parse_lines(lines.push(&buffer[..bytes_read]))?.into_iter();
+            while let Some(event) = events.next() {
+                if shutdown.is_shutdown() {
+                    return unhandled(std::iter::once(event).chain(events), &mut active_windows);
+                }
+                listener
+                    .event_primer_exec_async(event, &mut active_windows)
+                    .await?;
+            }
+            if bytes_read == 0 {
+                // If no bytes were read, we can assume the stream is closed
+                break;
+            }
+        }
+        Ok(vec![])
+    }
+    .await;
+    shutdown.reset();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use std::time::Duration;
+
+    #[test]
+    fn test_shutdown() -> crate::Result<()> {
+        let (mut stream, mut other) = UnixStream::pair()?;
+        let shutdown = ShutdownHandle::new();
+        let mut buffer = [0; 16];
+
+        other.write_all(b"bell>>\n")?;
+        let deadline = Instant::now() + Duration::from_secs(5);
+        assert_eq!(
+            read_blocking(&mut stream, &mut buffer, &shutdown, Some(deadline))?,
+            Some(7)
+        );
+        let deadline = Instant::now() + Duration::from_millis(20);
+        assert_eq!(
+            read_blocking(&mut stream, &mut buffer, &shutdown, Some(deadline))?,
+            None
+        );
+
+        shutdown.register_stream(&stream)?;
+        let handle = shutdown.clone();
+        let thread = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(20));
+            handle.shutdown();
+        });
+        assert_eq!(
+            read_blocking(&mut stream, &mut buffer, &shutdown, None)?,
+            None
+        );
+        let _ = thread.join();
+        assert!(shutdown.is_shutdown());
+
+        shutdown.reset();
+        assert!(!shutdown.is_shutdown());
+        Ok(())
+    }
+
+    #[test]
+    fn test_unhandled_events_are_primed() -> crate::Result<()> {
+        let events = super::super::event_parser(
+            "activewindow>>kitty,~\nactivewindowv2>>5d3a1f80\nbell>>\nactivewindow>>foot,~",
+        )?;
+        let events = unhandled(events.into_iter(), &mut vec![])?;
+        assert_eq!(events.len(), 2);
+        assert!(
+            matches!(&events[0], Event::ActiveWindowChanged(Some(data)) if data.class == "kitty")
+        );
+        assert_eq!(events[1], Event::Bell(None));
+        Ok(())
+    }
+}
@@ -31,8 +31,14 @@ use futures_lite::{Stream, StreamExt};
 /// ```
 #[must_use = "streams nothing unless polled"]
 pub struct EventStream {
-    stream: Pin<Box<dyn Stream<Item = crate::Result<Event>> + Send>>,
+    // built on the first poll, so it gets the kinds set with [Self::filter_kinds] by value
+    make: Option<Box<dyn FnOnce(KindSet) -> BoxedEventStream + Send>>,
+    stream: Option<BoxedEventStream>,
+    kinds: KindSet,
 }
+
+type BoxedEventStream = Pin<Box<dyn Stream<Item = crate::Result<Event>> + Send>>;
+
 impl Default for EventStream {
     fn default() -> Self {
         Self::new()
@@ -43,64 +49,134 @@ impl EventStream {
     /// Creates a new [EventStream]
     pub fn new() -> Self {
         use crate::async_import::*;
-        let stream = async_stream::try_stream! {
-        let mut stream: UnixStream = default_instance()?.get_event_stream_async().await?;
-            let mut active_windows = vec![];
-            loop {
-                let mut buffer = [0; 4096];
-                let bytes_read = stream.read(&mut buffer).await?;
-                if bytes_read == 0 {
-                    // If no bytes were read, we can assume the stream is closed
-                    break;
-                }
-                let buf = &buffer[..bytes_read];
-                let string = String::from_utf8_lossy(buf);
-                let parsed: Vec<Event> = event_parser(&string)?;
-                for event in parsed {
-                    for primed_event in event_primer_noexec(event, &mut active_windows)? {
-                        yield primed_event;
+        Self::from_filtered(|kinds| {
+            async_stream::try_stream! {
+                let mut stream: UnixStream = default_instance()?.get_event_stream_async().await?;
+                let mut active_windows = vec![];
+                let mut lines = EventLineBuffer::new();
+                loop {
+                    let mut buffer = [0; 4096];
+                    let bytes_read = stream.read(&mut buffer).await?;
+                    for line in lines.push(&buffer[..bytes_read]) {
+                        for event in event_parser(&line)? {
+                            if !kinds.contains(event.kind()) {
+                                continue;
+                            }
+                            for primed_event in event_primer_noexec(event, &mut active_windows)? {
+                                yield primed_event;
+                            }
+                        }
+                    }
+                    if bytes_read == 0 {
+                        // If no bytes were read, we can assume the stream is closed
+                        break;
                     }
                 }
             }
-        };
-        Self {
-            stream: Box::pin(stream),
-        }
+        })
     }
 
     /// Creates a new [EventStream]
     pub fn instance_new(instance: Instance) -> Self {
         use crate::async_import::*;
-        let stream = async_stream::try_stream! {
-        let mut stream: UnixStream = instance.get_event_stream_async().await?;
-            let mut active_windows = vec![];
-            loop {
-                let mut buffer = [0; 4096];
-                let bytes_read = stream.read(&mut buffer).await?;
-                if bytes_read == 0 {
-                    // If no bytes were read, we can assume the stream is closed
-                    break;
-                }
-                let buf = &buffer[..bytes_read];
-                let string = String::from_utf8_lossy(buf);
-                let parsed: Vec<Event> = event_parser(&string)?;
-                for event in parsed {
-                    for primed_event in event_primer_noexec(event, &mut active_windows)? {
-                        yield primed_event;
+        Self::from_filtered(move |kinds| {
+            async_stream::try_stream! {
+                let mut stream: UnixStream = instance.get_event_stream_async().await?;
+                let mut active_windows = vec![];
+                let mut lines = EventLineBuffer::new();
+                loop {
+                    let mut buffer = [0; 4096];
+                    let bytes_read = stream.read(&mut buffer).await?;
+                    for line in lines.push(&buffer[..bytes_read]) {
+                        for event in event_parser(&line)? {
+                            if !kinds.contains(event.kind()) {
+                                continue;
+                            }
+                            for primed_event in event_primer_noexec(event, &mut active_windows)? {
+                                yield primed_event;
+                            }
+                        }
+                    }
+                    if bytes_read == 0 {
+                        // If no bytes were read, we can assume the stream is closed
+                        break;
                     }
                 }
             }
-        };
+        })
+    }
+
+    /// Wraps a stream of events, made from the kinds [Self::filter_kinds] filters on
+    pub(super) fn from_filtered<S>(make: impl FnOnce(KindSet) -> S + Send + 'static) -> Self
+    where
+        S: Stream<Item = crate::Result<Event>> + Send + 'static,
+    {
         Self {
-            stream: Box::pin(stream),
+            make: Some(Box::new(move |kinds| Box::pin(make(kinds)))),
+            stream: None,
+            kinds: KindSet::DEFAULT,
         }
     }
+
+    /// Only yields events of the given kinds
+    ///
+    /// The other events are skipped right after being parsed, before any other work is done on them.
+    /// Without a filter every event but the legacy ones is yielded, see [EventKind::is_legacy]
+    ///
+    /// ```rust, no_run
+    /// use hyprland::event_listener::{EventKind, EventStream};
+    /// use futures_lite::StreamExt;
+    ///
+    /// #[tokio::main(flavor = "current_thread")]
+    /// async fn main() -> hyprland::Result<()> {
+    ///     let mut stream = EventStream::new()
+    ///         .filter_kinds(&[EventKind::WindowOpened, EventKind::WindowClosed]);
+    ///     while let Some(event) = stream.next().await {
+    ///         println!("{:?}", event?);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn filter_kinds(mut self, kinds: &[EventKind]) -> Self {
+        self.kinds = KindSet::from_kinds(kinds);
+        self
+    }
+
+    /// Only yields the data of the events carrying `T`
+    ///
+    /// ```rust, no_run
+    /// use hyprland::event_listener::{EventStream, WindowOpenEvent};
+    /// use futures_lite::StreamExt;
+    ///
+    /// #[tokio::main(flavor = "current_thread")]
+    /// async fn main() -> hyprland::Result<()> {
+    ///     let mut windows = EventStream::new().only::<WindowOpenEvent>();
+    ///     while let Some(window) = windows.next().await {
+    ///         println!("{} opened", window?.window_class);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn only<T: EventData>(self) -> impl Stream<Item = crate::Result<T>> + Send {
+        self.filter_kinds(&[T::KIND])
+            .filter_map(|event| match event {
+                Ok(event) => T::from_event(event).map(Ok),
+                Err(e) => Some(Err(e)),
+            })
+    }
 }
 
 impl Stream for EventStream {
     type Item = crate::Result<Event>;
 
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
-        self.as_mut().stream.poll_next(cx)
+        let this = &mut *self;
+        if let Some(make) = this.make.take() {
+            this.stream = Some(make(this.kinds));
+        }
+        match &mut this.stream {
+            Some(stream) => stream.as_mut().poll_next(cx),
+            None => Poll::Ready(None),
+        }
     }
 }
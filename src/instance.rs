@@ -129,6 +129,15 @@ impl Instance {
         Ok(crate::encoding::decode_ipc_response(&response))
     }
 
+    /// Returns a blocking iterator over the events of the instance
+    ///
+    /// The socket is connected on the first call to [Iterator::next], connection errors are
+    /// yielded from it
+    #[cfg(feature = "listener")]
+    pub fn events_blocking(&self) -> crate::event_listener::BlockingEvents {
+        crate::event_listener::BlockingEvents::new(self.clone())
+    }
+
     #[cfg(feature = "listener")]
     pub(crate) fn get_event_stream(&self) -> crate::Result<std::os::unix::net::UnixStream> {
         let stream = std::os::unix::net::UnixStream::connect(&self.event_socket_path)?;
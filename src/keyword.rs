@@ -44,26 +44,33 @@ impl std::fmt::Display for HyprColor {
 }
 
 /// A Gradiant made up of HyprColor(s) and an angle
-#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct HyprGradient {
-    /// First gradiant color
-    pub color0: HyprColor,
-    /// Second gradiant color
-    pub color1: Option<HyprColor>,
+    /// Gradiant color stops, between 1 and [HyprGradient::MAX_STOPS]
+    pub colors: Vec<HyprColor>,
     /// Angle in degrees
     pub angle: u32,
 }
 
+impl HyprGradient {
+    /// The maximum amount of color stops Hyprland accepts in a gradient
+    pub const MAX_STOPS: usize = 10;
+
+    /// Creates a new gradient, checking that the amount of color stops is valid
+    pub fn new(colors: Vec<HyprColor>, angle: u32) -> crate::Result<Self> {
+        if colors.is_empty() || colors.len() > Self::MAX_STOPS {
+            return Err(crate::HyprError::InvalidHyprGradiantFormat);
+        }
+        Ok(Self { colors, angle })
+    }
+}
+
 impl std::fmt::Display for HyprGradient {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let (color1, space) = match &self.color1 {
-            Some(s) => (s.to_string(), " "),
-            None => (String::from(""), ""),
-        };
-        f.write_fmt(format_args!(
-            "{}{}{} {}deg",
-            self.color0, space, color1, self.angle
-        ))
+        for color in &self.colors {
+            write!(f, "{color} ")?;
+        }
+        write!(f, "{}deg", self.angle)
     }
 }
 
@@ -92,23 +99,21 @@ impl std::fmt::Display for HyprRect {
 impl TryFrom<&str> for HyprRect {
     type Error = crate::HyprError;
 
+    /// Parses a rect using the css shorthand Hyprland uses for gaps,
+    /// e.g. `5`, `5 10`, `5 10 15` or `5 10 15 20`
     fn try_from(s: &str) -> Result<Self, Self::Error> {
-        let mut s = s.trim().split(" ");
-        let top = (s.next().ok_or(crate::HyprError::InvalidOptionValue)?)
-            .parse::<i64>()
-            .map_err(|_| crate::HyprError::InvalidOptionValue)?;
-        let right = (s.next().ok_or(crate::HyprError::InvalidOptionValue)?)
-            .parse::<i64>()
-            .map_err(|_| crate::HyprError::InvalidOptionValue)?;
-        let bottom = (s.next().ok_or(crate::HyprError::InvalidOptionValue)?)
-            .parse::<i64>()
-            .map_err(|_| crate::HyprError::InvalidOptionValue)?;
-        let left = (s.next().ok_or(crate::HyprError::InvalidOptionValue)?)
-            .parse::<i64>()
+        let sides = s
+            .split_whitespace()
+            .map(|side| side.parse::<i64>())
+            .collect::<Result<Vec<_>, _>>()
             .map_err(|_| crate::HyprError::InvalidOptionValue)?;
-        if s.next().is_some() {
-            return Err(crate::error::HyprError::InvalidOptionValue);
-        }
+        let (top, right, bottom, left) = match sides[..] {
+            [all] => (all, all, all, all),
+            [vertical, horizontal] => (vertical, horizontal, vertical, horizontal),
+            [top, horizontal, bottom] => (top, horizontal, bottom, horizontal),
+            [top, right, bottom, left] => (top, right, bottom, left),
+            _ => return Err(crate::HyprError::InvalidOptionValue),
+        };
 
         Ok(Self {
             top,
@@ -126,7 +131,7 @@ pub enum Custom {
     HyprColor(HyprColor),
     /// Gradiant Variant for Custom field
     HyprGradient(HyprGradient),
-    /// A general rect made of top, right, bottom, left
+    /// A general rect made of top, right, bottom, left, also used for css gaps
     HyprRect(HyprRect),
 }
 
@@ -180,7 +185,7 @@ impl HyprColor {
                 // b16 parse (e.g., "rgba(FF00AA7F)")
                 false => {
                     let s = s.trim();
-                    if s.len() != 6 {
+                    if s.len() != 8 {
                         return None;
                     }
                     let i = u32::from_str_radix(s, 16).ok()?;
@@ -246,54 +251,26 @@ impl TryFrom<&str> for HyprGradient {
     type Error = crate::HyprError;
 
     fn try_from(s: &str) -> Result<Self, Self::Error> {
-        let mut a = None;
         let s = s.trim().replace(", ", ",");
-        let mut s = s.split(" ");
-        let color0 = HyprColor::try_from(
-            s.next()
-                .ok_or(crate::HyprError::InvalidHyprGradiantFormat)?,
-        )?;
-        let mut color1 = None;
-        let c1_angle = s
-            .next()
-            .ok_or(crate::HyprError::InvalidHyprGradiantFormat)?;
-        if c1_angle.contains("deg") {
-            let tmp = c1_angle
-                .strip_suffix("deg")
-                .ok_or(crate::HyprError::InvalidHyprGradiantFormat)?;
-            a = Some(
-                tmp.parse::<u32>()
-                    .map_err(|_| crate::HyprError::InvalidHyprGradiantFormat)?,
-            );
-        } else {
-            color1 = Some(HyprColor::try_from(c1_angle)?)
-        }
-
-        let angle = match a {
-            Some(a) => a,
-            None => {
-                let c1_angle = s
-                    .next()
-                    .ok_or(crate::HyprError::InvalidHyprGradiantFormat)?;
-                let tmp = c1_angle
-                    .strip_suffix("deg")
-                    .ok_or(crate::HyprError::InvalidHyprGradiantFormat)?;
-                match tmp.parse::<u32>() {
-                    Ok(i) => i,
-                    Err(_) => return Err(crate::HyprError::InvalidHyprGradiantFormat),
+        let mut colors = vec![];
+        let mut angle = None;
+        for part in s.split_whitespace() {
+            if angle.is_some() {
+                // the angle has to be the last part of a gradient
+                return Err(crate::HyprError::InvalidHyprGradiantFormat);
+            }
+            match part.strip_suffix("deg") {
+                Some(deg) => {
+                    angle = Some(
+                        deg.parse::<u32>()
+                            .map_err(|_| crate::HyprError::InvalidHyprGradiantFormat)?,
+                    )
                 }
+                None => colors.push(HyprColor::try_from(part)?),
             }
-        };
-
-        if s.next().is_some() {
-            return Err(crate::HyprError::InvalidHyprGradiantFormat);
         }
 
-        Ok(HyprGradient {
-            color0,
-            color1,
-            angle,
-        })
+        Self::new(colors, angle.unwrap_or_default())
     }
 }
 
@@ -302,11 +279,13 @@ impl TryFrom<&str> for Custom {
 
     fn try_from(s: &str) -> Result<Self, Self::Error> {
         let c = s.trim().replace(", ", ",");
-        let c = c.split(" ");
-        let c = c.count();
-        if c == 4 {
+        // an argb color such as `11111111` is all digits as well, but no gap is that large
+        let is_color = |part: &str| part.len() == 8 && part.chars().all(|c| c.is_ascii_hexdigit());
+        if c.split_whitespace()
+            .all(|side| !is_color(side) && side.parse::<i64>().is_ok())
+        {
             Ok(Self::HyprRect(HyprRect::try_from(s)?))
-        } else if c > 1 {
+        } else if c.split_whitespace().count() > 1 || c.ends_with("deg") {
             Ok(Self::HyprGradient(HyprGradient::try_from(s)?))
         } else {
             Ok(Self::HyprColor(HyprColor::try_from(s)?))
@@ -333,12 +312,16 @@ pub enum OptionValue {
     Int(i64),
     /// A floating point (64-point)
     Float(f64),
+    /// A boolean
+    Bool(bool),
     /// A string
     String(String),
-    /// A hyprland Color or Gradiant
+    /// A hyprland Color, Gradiant or Rect (css gaps)
     Custom(Custom),
     /// A Vector of 2 ints
     Vec2([i64; 2]),
+    /// A Vector of 2 floats
+    FloatVec2([f64; 2]),
     /// Could not parse value
     Unknown(String),
 }
@@ -348,9 +331,11 @@ impl std::fmt::Display for OptionValue {
         match self {
             OptionValue::Int(i) => f.write_fmt(format_args!("{:0}", i)),
             OptionValue::Float(fl) => f.write_fmt(format_args!("{:0}", fl)),
+            OptionValue::Bool(b) => f.write_fmt(format_args!("{}", b)),
             OptionValue::String(s) => f.write_fmt(format_args!("{}", s)),
             OptionValue::Custom(custom) => f.write_fmt(format_args!("{}", custom)),
             OptionValue::Vec2(v) => f.write_fmt(format_args!("{} {}", v[0], v[1])),
+            OptionValue::FloatVec2(v) => f.write_fmt(format_args!("{} {}", v[0], v[1])),
             OptionValue::Unknown(s) => f.write_fmt(format_args!("Unknown Value type ({})", s)),
         }
     }
@@ -368,14 +353,43 @@ impl IsString for &str {}
 
 impl<Str: ToString + IsString> From<Str> for OptionValue {
     fn from(str: Str) -> Self {
-        if let Ok(c) = Custom::try_from(str.to_string().as_str()) {
-            OptionValue::Custom(c)
-        } else {
-            OptionValue::String(str.to_string())
-        }
+        // strings are sent as they are, guessing would turn "2 4" into a rect or "fade" into a
+        // color. Use [HyprColor], [HyprGradient] or [HyprRect] for those
+        OptionValue::String(str.to_string())
     }
 }
 
+macro_rules! from_value {
+    ($opt:ident => $($ty:ty),+) => {
+        $(
+            impl From<$ty> for OptionValue {
+                fn from(value: $ty) -> Self {
+                    OptionValue::$opt(value.into())
+                }
+            }
+        )+
+    };
+}
+from_value!(Int => i8, i16, i32, i64, u8, u16, u32);
+from_value!(Float => f32, f64);
+from_value!(Bool => bool);
+from_value!(Vec2 => [i64; 2]);
+from_value!(FloatVec2 => [f64; 2]);
+from_value!(Custom => Custom);
+
+macro_rules! from_custom {
+    ($($ty:ident),+) => {
+        $(
+            impl From<$ty> for OptionValue {
+                fn from(value: $ty) -> Self {
+                    OptionValue::Custom(Custom::$ty(value))
+                }
+            }
+        )+
+    };
+}
+from_custom!(HyprColor, HyprGradient, HyprRect);
+
 macro_rules! match_unknown {
     ($k:expr, $v:expr, $opt:ident) => {
         match $v {
@@ -385,32 +399,38 @@ macro_rules! match_unknown {
     };
 }
 
+/// The value types Hyprland can return for an option
+const OPTION_TYPES: &[&str] = &["int", "float", "bool", "str", "custom", "vec2"];
+
 impl TryFrom<&OptionRaw> for OptionValue {
     type Error = HyprError;
     fn try_from(raw: &OptionRaw) -> crate::Result<Self> {
-        Ok(match raw.value.iter().next() {
+        let value = OPTION_TYPES
+            .iter()
+            .find_map(|k| raw.value.get_key_value(*k))
+            .or_else(|| raw.value.iter().next());
+        Ok(match value {
             Some((k, v)) => match k.as_str() {
                 "int" => match_unknown!(raw.json, v.as_i64(), Int),
                 "float" => match_unknown!(raw.json, v.as_f64(), Float),
+                "bool" => match_unknown!(raw.json, v.as_bool(), Bool),
                 "str" => match_unknown!(raw.json, v.as_str().map(|v| v.to_string()), String),
                 "custom" => match_unknown!(
                     raw.json,
                     v.as_str().and_then(|v| Custom::try_from(v).ok()),
                     Custom
                 ),
-                "vec2" => {
-                    if let Some(a) = v.as_array()
-                        && a.len() == 2
-                        && a[0].is_i64()
-                        && a[1].is_i64()
-                    {
-                        return Ok(OptionValue::Vec2([
-                            a[0].as_i64().ok_or(HyprError::InvalidOptionValue)?,
-                            a[1].as_i64().ok_or(HyprError::InvalidOptionValue)?,
-                        ]));
-                    }
-                    OptionValue::Unknown(raw.json.to_string())
-                }
+                "vec2" => match v.as_array().map(|a| a.as_slice()) {
+                    Some([x, y]) if x.is_i64() && y.is_i64() => OptionValue::Vec2([
+                        x.as_i64().ok_or(HyprError::InvalidOptionValue)?,
+                        y.as_i64().ok_or(HyprError::InvalidOptionValue)?,
+                    ]),
+                    Some([x, y]) if x.is_number() && y.is_number() => OptionValue::FloatVec2([
+                        x.as_f64().ok_or(HyprError::InvalidOptionValue)?,
+                        y.as_f64().ok_or(HyprError::InvalidOptionValue)?,
+                    ]),
+                    _ => OptionValue::Unknown(raw.json.to_string()),
+                },
                 _ => OptionValue::Unknown(raw.json.to_string()),
             },
             None => OptionValue::Unknown(raw.json.to_string()),
@@ -475,12 +495,19 @@ impl Keyword {
         key: Str,
         value: Opt,
     ) -> crate::Result<()> {
+        let value = match value.into() {
+            OptionValue::Unknown(_) => {
+                return Err(crate::HyprError::InvalidOptionValue);
+            }
+            x => x,
+        };
+
         instance
             .write_to_socket_async(command!(
                 Empty,
                 "keyword {} {}",
                 key.to_string(),
-                value.into().to_string()
+                value.to_string()
             ))
             .await?;
         Ok(())
@@ -540,3 +567,83 @@ impl Keyword {
         Ok(keyword)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[track_caller]
+    fn parse_raw(json: &str) -> OptionValue {
+        #[allow(clippy::unwrap_used)]
+        let mut raw: OptionRaw = serde_json::from_str(json).unwrap();
+        raw.json = json.to_string();
+        #[allow(clippy::unwrap_used)]
+        OptionValue::try_from(&raw).unwrap()
+    }
+
+    #[track_caller]
+    fn check_round_trip(custom: &str, expected: &str) {
+        #[allow(clippy::unwrap_used)]
+        let custom = Custom::try_from(custom).unwrap();
+        assert_eq!(custom.to_string(), expected);
+        #[allow(clippy::unwrap_used)]
+        let reparsed = Custom::try_from(custom.to_string().as_str()).unwrap();
+        assert_eq!(reparsed.to_string(), expected);
+    }
+
+    #[test]
+    fn test_option_raw_types() {
+        let value = parse_raw(r#"{"option":"general:border_size","int":2,"set":true}"#);
+        assert!(matches!(value, OptionValue::Int(2)));
+        let value = parse_raw(r#"{"option":"misc:vfr","bool":true,"set":false}"#);
+        assert!(matches!(value, OptionValue::Bool(true)));
+        let value = parse_raw(r#"{"option":"decoration:shadow:offset","vec2":[2,4],"set":true}"#);
+        assert!(matches!(value, OptionValue::Vec2([2, 4])));
+        let value = parse_raw(r#"{"option":"cursor:hotspot_padding","vec2":[0.5,1.0],"set":true}"#);
+        assert!(matches!(value, OptionValue::FloatVec2([0.5, 1.0])));
+        let value = parse_raw(r#"{"option":"general:gaps_out","custom":"5 10 5 10","set":true}"#);
+        assert_eq!(value.to_string(), "5 10 5 10");
+        let value = parse_raw(r#"{"option":"foo","unknowntype":1,"set":true}"#);
+        assert!(matches!(value, OptionValue::Unknown(_)));
+    }
+
+    #[test]
+    fn test_css_gaps() {
+        check_round_trip("5", "5 5 5 5");
+        check_round_trip("5 10", "5 10 5 10");
+        check_round_trip("5 10 15", "5 10 15 10");
+        check_round_trip("5 10 15 20", "5 10 15 20");
+        assert!(HyprRect::try_from("1 2 3 4 5").is_err());
+        // all digit argb colors aren't gaps
+        check_round_trip("11111111", "rgba(11111111)");
+    }
+
+    #[test]
+    fn test_gradients() {
+        check_round_trip(
+            "rgba(33ccffee) rgba(00ff99ee) 45deg",
+            "rgba(33ccffee) rgba(00ff99ee) 45deg",
+        );
+        check_round_trip("ff33ccff ff00ff99", "rgba(33ccffff) rgba(00ff99ff) 0deg");
+        check_round_trip("11111111 22222222", "rgba(11111111) rgba(22222222) 0deg");
+        check_round_trip(
+            "rgb(ff0000) rgb(00ff00) rgb(0000ff) rgba(255, 255, 255, 1.0) 90deg",
+            "rgba(ff0000ff) rgba(00ff00ff) rgba(0000ffff) rgba(ffffffff) 90deg",
+        );
+        let eleven = ["rgb(ffffff)"; 11].join(" ");
+        assert!(HyprGradient::try_from(eleven.as_str()).is_err());
+        assert!(HyprGradient::try_from("45deg rgb(ffffff)").is_err());
+    }
+
+    #[test]
+    fn test_set_values() {
+        assert_eq!(OptionValue::from(true).to_string(), "true");
+        assert_eq!(OptionValue::from(5).to_string(), "5");
+        assert_eq!(OptionValue::from("5").to_string(), "5");
+        assert_eq!(OptionValue::from([0.5, 2.0]).to_string(), "0.5 2");
+        assert_eq!(OptionValue::from("reset").to_string(), "reset");
+        assert_eq!(OptionValue::from("2 4").to_string(), "2 4");
+        assert_eq!(OptionValue::from("fade").to_string(), "fade");
+        assert_eq!(OptionValue::from("cafe").to_string(), "cafe");
+    }
+}
@@ -72,8 +72,15 @@ mod async_import {
     pub use async_net::unix::UnixStream;
     #[cfg(all(feature = "async-lite", not(feature = "tokio")))]
     pub use futures_lite::io::{AsyncReadExt, AsyncWriteExt};
+    #[cfg(all(feature = "dispatch", feature = "tokio"))]
+    pub use tokio::time::sleep;
     #[cfg(feature = "tokio")]
     pub use tokio::{io::AsyncReadExt, io::AsyncWriteExt, net::UnixStream};
+
+    #[cfg(all(feature = "dispatch", feature = "async-lite", not(feature = "tokio")))]
+    pub async fn sleep(duration: std::time::Duration) {
+        async_io::Timer::after(duration).await;
+    }
 }
 
 /// This type provides the result type used everywhere in Hyprland-rs
@@ -3,6 +3,7 @@
 //! This module provides shared private and public functions, structs, enum, and types
 use derive_more::Display;
 use serde::{Deserialize, Serialize};
+use serde_repr::{Deserialize_repr, Serialize_repr};
 use std::hash::{Hash, Hasher};
 use std::path::PathBuf;
 use std::{env, fmt};
@@ -169,6 +170,55 @@ impl Hash for WorkspaceType {
     }
 }
 
+/// This enum provides the different monitor transforms
+#[derive(Serialize_repr, Deserialize_repr, Debug, Clone, PartialEq, Eq, Copy)]
+#[repr(u8)]
+pub enum Transforms {
+    /// No transform
+    Normal = 0,
+    /// Rotated 90 degrees
+    Normal90 = 1,
+    /// Rotated 180 degrees
+    Normal180 = 2,
+    /// Rotated 270 degrees
+    Normal270 = 3,
+    /// Flipped
+    Flipped = 4,
+    /// Flipped and rotated 90 degrees
+    Flipped90 = 5,
+    /// Flipped and rotated 180 degrees
+    Flipped180 = 6,
+    /// Flipped and rotated 270 degrees
+    Flipped270 = 7,
+}
+
+/// A keybinding returned from the binds command
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Bind {
+    /// Is it locked?
+    pub locked: bool,
+    /// Is it a mouse bind?
+    pub mouse: bool,
+    /// Does it execute on release?
+    pub release: bool,
+    /// Can it be held?
+    pub repeat: bool,
+    /// It's modmask
+    pub modmask: u16,
+    /// The submap its apart of
+    pub submap: String,
+    /// The key
+    pub key: String,
+    /// The keycode
+    pub keycode: i16,
+    /// The dispatcher to be executed
+    pub dispatcher: String,
+    /// The dispatcher arg
+    pub arg: String,
+    /// description from bind[d]
+    pub description: String,
+}
+
 pub(crate) fn get_hypr_path() -> crate::Result<PathBuf> {
     let mut buf = if let Some(runtime_path) = env::var_os("XDG_RUNTIME_DIR") {
         std::path::PathBuf::from(runtime_path)
@@ -268,6 +318,56 @@ pub enum Mod {
     ALT,
     #[display("CTRL")]
     CTRL,
+    #[display("CAPS")]
+    CAPS,
+    #[display("MOD2")]
+    MOD2,
+    #[display("MOD3")]
+    MOD3,
+    #[display("MOD5")]
+    MOD5,
     #[display("")]
     NONE,
 }
+
+impl Mod {
+    /// All the mods that have a bit in a modmask, in the order they are usually written
+    pub const ALL: [Mod; 8] = [
+        Mod::SUPER,
+        Mod::CTRL,
+        Mod::ALT,
+        Mod::SHIFT,
+        Mod::CAPS,
+        Mod::MOD2,
+        Mod::MOD3,
+        Mod::MOD5,
+    ];
+
+    /// The bit of this mod in a Hyprland modmask
+    pub fn mask(self) -> u16 {
+        match self {
+            Mod::SHIFT => 1 << 0,
+            Mod::CAPS => 1 << 1,
+            Mod::CTRL => 1 << 2,
+            Mod::ALT => 1 << 3,
+            Mod::MOD2 => 1 << 4,
+            Mod::MOD3 => 1 << 5,
+            Mod::SUPER => 1 << 6,
+            Mod::MOD5 => 1 << 7,
+            Mod::NONE => 0,
+        }
+    }
+
+    /// Decodes a Hyprland modmask into its mods, in the order of [Mod::ALL]
+    pub fn from_modmask(modmask: u16) -> Vec<Mod> {
+        Mod::ALL
+            .into_iter()
+            .filter(|m| modmask & m.mask() != 0)
+            .collect()
+    }
+
+    /// Encodes mods into a Hyprland modmask
+    pub fn to_modmask(mods: &[Mod]) -> u16 {
+        mods.iter().fold(0, |mask, m| mask | m.mask())
+    }
+}
@@ -1,4 +1,8 @@
 //! This module provides unsafe impls for several types, mainly for FFI purposes. Do not use unless you know what you are doing.
+//!
+//! [EventListener](crate::event_listener::EventListener) shares its handlers through an `Rc`, so it
+//! is never [Send] or [Sync]. To run a closure based listener on another thread or task, use
+//! [SendEventListener](crate::event_listener::SendEventListener) instead
 
 /// unsafe implementations for event listener structs
 #[cfg(feature = "listener")]
@@ -8,9 +12,6 @@ pub mod listeners {
     unsafe impl Send for AsyncEventListener {}
     unsafe impl Sync for AsyncEventListener {}
 
-    unsafe impl Send for EventListener {}
-    unsafe impl Sync for EventListener {}
-
     unsafe impl Send for WindowMoveEvent {}
     unsafe impl Sync for WindowMoveEvent {}
 